@@ -2,6 +2,12 @@ use std::process::Command;
 
 fn main()
 {
+	// The pod5 C++ library is only needed for the ffi backend.
+	if std::env::var_os("CARGO_FEATURE_FFI").is_none()
+	{
+		return;
+	}
+
 	let _ = Command::new("python")
 		.args([
 			"-m",
@@ -0,0 +1,320 @@
+#[cfg(feature = "ffi")]
+use decapod::merge::{merge, DuplicateReads};
+#[cfg(not(feature = "ffi"))]
+use decapod::native::reader::{Read, Reader, DEFAULT_FIELDS};
+#[cfg(feature = "ffi")]
+use decapod::reader::{Read, Reader, DEFAULT_FIELDS};
+#[cfg(feature = "ffi")]
+use decapod::subset::subset;
+
+use clap::{Parser, Subcommand};
+#[cfg(feature = "ffi")]
+use clap::ValueEnum;
+
+#[cfg(feature = "ffi")]
+use std::collections::{hash_map::Entry, HashMap};
+use std::error::Error;
+#[cfg(feature = "ffi")]
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+/// Inspect and manipulate pod5 files.
+#[derive(Parser)]
+#[command(name = "decapod", version, about)]
+struct Cli
+{
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command
+{
+	/// Print file info, read counts and run info.
+	Inspect
+	{
+		/// pod5 files or directories containing pod5 files.
+		#[arg(required = true)]
+		inputs: Vec<PathBuf>,
+	},
+	/// Print read metadata as a tab separated table.
+	View
+	{
+		/// pod5 files or directories containing pod5 files.
+		#[arg(required = true)]
+		inputs: Vec<PathBuf>,
+		/// Comma separated fields to print, defaults to all fields.
+		#[arg(short, long, value_delimiter = ',')]
+		fields: Option<Vec<String>>,
+		/// Omit the header line.
+		#[arg(long)]
+		no_header: bool,
+	},
+	/// Print every read id.
+	Ids
+	{
+		/// pod5 files or directories containing pod5 files.
+		#[arg(required = true)]
+		inputs: Vec<PathBuf>,
+	},
+	/// Split reads into multiple pod5 files.
+	#[cfg(feature = "ffi")]
+	Subset
+	{
+		/// pod5 files or directories containing pod5 files.
+		#[arg(required = true)]
+		inputs: Vec<PathBuf>,
+		/// Tab separated file of read ids and their output file name.
+		#[arg(short, long)]
+		table: PathBuf,
+		/// Directory to write output files to.
+		#[arg(short, long, default_value = ".")]
+		output: PathBuf,
+	},
+	/// Merge pod5 files into a single pod5 file.
+	#[cfg(feature = "ffi")]
+	Merge
+	{
+		/// pod5 files or directories containing pod5 files.
+		#[arg(required = true)]
+		inputs: Vec<PathBuf>,
+		/// The merged pod5 file to create.
+		#[arg(short, long)]
+		output: PathBuf,
+		/// How to handle read ids found in more than one file.
+		#[arg(short, long, value_enum, default_value_t = Duplicates::Keep)]
+		duplicates: Duplicates,
+	},
+}
+
+#[cfg(feature = "ffi")]
+#[derive(Clone, Copy, ValueEnum)]
+enum Duplicates
+{
+	Keep,
+	Skip,
+	Error,
+}
+
+#[cfg(feature = "ffi")]
+impl From<Duplicates> for DuplicateReads
+{
+	fn from(duplicates: Duplicates) -> DuplicateReads
+	{
+		match duplicates
+		{
+			Duplicates::Keep => DuplicateReads::Keep,
+			Duplicates::Skip => DuplicateReads::Skip,
+			Duplicates::Error => DuplicateReads::Error,
+		}
+	}
+}
+
+fn inspect(inputs: Vec<PathBuf>) -> Result<(), Box<dyn Error>>
+{
+	let reader = Reader::from_vec(inputs, None)?;
+
+	for info in reader.info()
+	{
+		let info = info?;
+		println!("file_identifier = {}", info.file_identifier());
+		println!(
+			"version = {}.{}.{}",
+			info.major_version(),
+			info.minor_version(),
+			info.revision_version()
+		);
+	}
+
+	println!("read_count = {}", reader.count()?);
+
+	for run_info in reader.run_info_iter()
+	{
+		println!();
+		print!("{}", run_info?);
+	}
+
+	Ok(())
+}
+
+fn view_field(read: &Read, field: &str, acquisition_id: &str) -> Result<String, Box<dyn Error>>
+{
+	let calibration = read.calibration();
+
+	Ok(match field
+	{
+		"read_id" => read.uuid().to_string(),
+		"read_number" => read.read_number().to_string(),
+		"start_sample" => read.start_sample().to_string(),
+		"median_before" => read.median_before().to_string(),
+		"channel" => read.channel().to_string(),
+		"well" => read.well().to_string(),
+		"pore_type" => read.pore_type_string()?,
+		"calibration_offset" => calibration.offset().to_string(),
+		"calibration_scale" => calibration.scale().to_string(),
+		"end_reason" => read.end_reason().to_string(),
+		"end_reason_forced" => read.end_reason_forced().to_string(),
+		"run_info" => acquisition_id.to_string(),
+		"num_minknow_events" => read.num_minknow_events().to_string(),
+		"tracked_scaling_scale" => read.tracked_scaling_scale().to_string(),
+		"tracked_scaling_shift" => read.tracked_scaling_shift().to_string(),
+		"predicted_scaling_scale" => read.predicted_scaling_scale().to_string(),
+		"predicted_scaling_shift" => read.predicted_scaling_shift().to_string(),
+		"num_reads_since_mux_change" => read.num_reads_since_mux_change().to_string(),
+		"time_since_mux_change" => read.time_since_mux_change().to_string(),
+		"signal_row_count" => read.signal_row_count().to_string(),
+		"num_samples" => read.num_samples().to_string(),
+		_ => return Err(format!("unknown field: {}", field).into()),
+	})
+}
+
+fn view(
+	inputs: Vec<PathBuf>,
+	fields: Option<Vec<String>>,
+	no_header: bool,
+) -> Result<(), Box<dyn Error>>
+{
+	let fields = fields.unwrap_or_else(|| DEFAULT_FIELDS.iter().map(|f| f.to_string()).collect());
+	if let Some(field) = fields.iter().find(|f| !DEFAULT_FIELDS.contains(&f.as_str()))
+	{
+		return Err(format!("unknown field: {}", field).into());
+	}
+
+	let reader = Reader::from_vec(inputs, None)?;
+	let mut out = BufWriter::new(io::stdout().lock());
+
+	if !no_header
+	{
+		writeln!(out, "{}", fields.join("\t"))?;
+	}
+
+	// Run info is looked up once per batch rather than for every read.
+	#[cfg(feature = "ffi")]
+	for batch in reader.batch_records_iter(None)
+	{
+		let batch = batch?;
+		let mut acquisition_ids: HashMap<i16, String> = HashMap::new();
+
+		for read in batch.reads()
+		{
+			let acquisition_id = match acquisition_ids.entry(read.run_info_num())
+			{
+				Entry::Occupied(entry) => entry.into_mut(),
+				Entry::Vacant(entry) => entry.insert(read.run_info()?.acquisition_id()?.to_string()),
+			};
+			view_row(&mut out, &read, &fields, acquisition_id)?;
+		}
+	}
+
+	// Native reads hold their acquisition id, so there is nothing to look up.
+	#[cfg(not(feature = "ffi"))]
+	for read in reader.reads_iter(None)
+	{
+		let read = read?;
+		let acquisition_id = read.run_info()?.acquisition_id()?.to_string();
+		view_row(&mut out, &read, &fields, &acquisition_id)?;
+	}
+
+	Ok(())
+}
+
+fn view_row(
+	out: &mut impl Write,
+	read: &Read,
+	fields: &[String],
+	acquisition_id: &str,
+) -> Result<(), Box<dyn Error>>
+{
+	let values = fields
+		.iter()
+		.map(|field| view_field(read, field, acquisition_id))
+		.collect::<Result<Vec<String>, Box<dyn Error>>>()?;
+	writeln!(out, "{}", values.join("\t"))?;
+
+	Ok(())
+}
+
+fn ids(inputs: Vec<PathBuf>) -> Result<(), Box<dyn Error>>
+{
+	let reader = Reader::from_vec(inputs, None)?;
+	let mut out = BufWriter::new(io::stdout().lock());
+
+	for read_id in reader.read_ids()?
+	{
+		writeln!(out, "{}", read_id)?;
+	}
+
+	Ok(())
+}
+
+#[cfg(feature = "ffi")]
+fn subset_table(
+	inputs: Vec<PathBuf>,
+	table: PathBuf,
+	output: PathBuf,
+) -> Result<(), Box<dyn Error>>
+{
+	let mut mapping = HashMap::new();
+	for (line_number, line) in fs::read_to_string(&table)?.lines().enumerate()
+	{
+		let mut columns = line.split('\t');
+		let (Some(read_id), Some(target)) = (columns.next(), columns.next())
+		else
+		{
+			return Err(format!("line {}: expected read_id and output columns", line_number + 1).into());
+		};
+
+		// Allow for a header line.
+		if line_number == 0 && read_id == "read_id"
+		{
+			continue;
+		}
+
+		mapping.insert(uuid::Uuid::parse_str(read_id.trim())?, output.join(target.trim()));
+	}
+
+	let reader = Reader::from_vec(inputs, None)?;
+	let counts = subset(&reader, &mapping, None)?;
+
+	for (path, count) in counts
+	{
+		println!("{}\t{}", path.display(), count);
+	}
+
+	Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>>
+{
+	let cli = Cli::parse();
+
+	match cli.command
+	{
+		Command::Inspect { inputs } => inspect(inputs),
+		Command::View {
+			inputs,
+			fields,
+			no_header,
+		} => view(inputs, fields, no_header),
+		Command::Ids { inputs } => ids(inputs),
+		#[cfg(feature = "ffi")]
+		Command::Subset {
+			inputs,
+			table,
+			output,
+		} => subset_table(inputs, table, output),
+		#[cfg(feature = "ffi")]
+		Command::Merge {
+			inputs,
+			output,
+			duplicates,
+		} =>
+		{
+			let reader = Reader::from_vec(inputs, None)?;
+			let count = merge(&reader, output, duplicates.into(), None)?;
+			println!("{}", count);
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,429 @@
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Read fields used when none are requested.
+pub const DEFAULT_FIELDS: [&str; 21] = [
+	"read_id",
+	"read_number",
+	"start_sample",
+	"median_before",
+	"channel",
+	"well",
+	"pore_type",
+	"calibration_offset",
+	"calibration_scale",
+	"end_reason",
+	"end_reason_forced",
+	"run_info",
+	"num_minknow_events",
+	"tracked_scaling_scale",
+	"tracked_scaling_shift",
+	"predicted_scaling_scale",
+	"predicted_scaling_shift",
+	"num_reads_since_mux_change",
+	"time_since_mux_change",
+	"signal_row_count",
+	"num_samples",
+];
+
+/// Convert a raw ADC sample to picoamps.
+pub(crate) fn adc_to_pa(sample: i16, offset: f32, scale: f32) -> f32
+{
+	(sample as f32 + offset) * scale
+}
+
+/// Convert picoamps back to the nearest raw ADC sample.
+pub(crate) fn pa_to_adc(sample: f32, offset: f32, scale: f32) -> i16
+{
+	(sample / scale - offset)
+		.round()
+		.clamp(i16::MIN as f32, i16::MAX as f32) as i16
+}
+
+/// Owned read information used when adding reads to a [`crate::writer::Writer`].
+///
+/// `pore_type` and `run_info` are indices returned by [`crate::writer::Writer::add_pore`]
+/// and [`crate::writer::Writer::add_run_info`] for the file being written.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Default)]
+pub struct ReadData
+{
+	/// The read id.
+	pub read_id: uuid::Uuid,
+	/// The read number.
+	pub read_number: u32,
+	/// Number of samples in the channel before read was taken.
+	pub start_sample: u64,
+	/// Current level before read taken.
+	pub median_before: f32,
+	/// 1-indexed channel.
+	pub channel: u16,
+	/// 1-indexed well number.
+	pub well: u8,
+	/// Pore type index within the file being written.
+	pub pore_type: i16,
+	/// Calibration offset.
+	pub calibration_offset: f32,
+	/// Calibration scale.
+	pub calibration_scale: f32,
+	/// See [crate::endreason::EndReason]
+	pub end_reason: crate::endreason::EndReason,
+	/// True if the read was forcefully ended.
+	pub end_reason_forced: bool,
+	/// Run info index within the file being written.
+	pub run_info: i16,
+	/// Number of minknow events that the read contains.
+	pub num_minknow_events: u64,
+	/// Tracked scaling scale.
+	pub tracked_scaling_scale: f32,
+	/// Tracked scaling shift.
+	pub tracked_scaling_shift: f32,
+	/// Predicted scaling scale.
+	pub predicted_scaling_scale: f32,
+	/// Predicted scaling shift.
+	pub predicted_scaling_shift: f32,
+	/// Number of reads since last mux change.
+	pub num_reads_since_mux_change: u32,
+	/// Time in seconds since last mux change.
+	pub time_since_mux_change: f32,
+	/// The uncompressed signal for the read.
+	pub signal: Vec<i16>,
+}
+
+impl ReadData
+{
+	/// Start building a read with the given read id, all other fields start zeroed.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use decapod::reader::ReadData;
+	/// use decapod::endreason::EndReason;
+	///
+	/// let read = ReadData::builder(uuid::Uuid::new_v4())
+	///     .channel(100)
+	///     .well(1)
+	///     .end_reason(EndReason::SignalPositive)
+	///     .signal(vec![0i16; 4000])
+	///     .build();
+	/// ```
+	pub fn builder(read_id: uuid::Uuid) -> ReadDataBuilder
+	{
+		ReadDataBuilder {
+			inner: ReadData {
+				read_id,
+				..Default::default()
+			},
+		}
+	}
+
+	/// Number of signal samples.
+	pub fn num_samples(&self) -> u64
+	{
+		self.signal.len() as u64
+	}
+
+	/// The signal calibrated to picoamps, using the read's calibration offset and scale.
+	pub fn signal_pa(&self) -> Vec<f32>
+	{
+		self.signal
+			.iter()
+			.map(|sample| adc_to_pa(*sample, self.calibration_offset, self.calibration_scale))
+			.collect()
+	}
+}
+
+/// Builder for [`ReadData`], see [`ReadData::builder`].
+#[derive(Debug, Clone)]
+pub struct ReadDataBuilder
+{
+	inner: ReadData,
+}
+
+impl ReadDataBuilder
+{
+	/// The read number.
+	pub fn read_number(mut self, read_number: u32) -> ReadDataBuilder
+	{
+		self.inner.read_number = read_number;
+		self
+	}
+
+	/// Number of samples in the channel before read was taken.
+	pub fn start_sample(mut self, start_sample: u64) -> ReadDataBuilder
+	{
+		self.inner.start_sample = start_sample;
+		self
+	}
+
+	/// Current level before read taken.
+	pub fn median_before(mut self, median_before: f32) -> ReadDataBuilder
+	{
+		self.inner.median_before = median_before;
+		self
+	}
+
+	/// 1-indexed channel.
+	pub fn channel(mut self, channel: u16) -> ReadDataBuilder
+	{
+		self.inner.channel = channel;
+		self
+	}
+
+	/// 1-indexed well number.
+	pub fn well(mut self, well: u8) -> ReadDataBuilder
+	{
+		self.inner.well = well;
+		self
+	}
+
+	/// Pore type index returned by [`crate::writer::Writer::add_pore`].
+	pub fn pore_type(mut self, pore_type: i16) -> ReadDataBuilder
+	{
+		self.inner.pore_type = pore_type;
+		self
+	}
+
+	/// Calibration offset and scale.
+	pub fn calibration(mut self, offset: f32, scale: f32) -> ReadDataBuilder
+	{
+		self.inner.calibration_offset = offset;
+		self.inner.calibration_scale = scale;
+		self
+	}
+
+	/// See [crate::endreason::EndReason]
+	pub fn end_reason(mut self, end_reason: crate::endreason::EndReason) -> ReadDataBuilder
+	{
+		self.inner.end_reason = end_reason;
+		self
+	}
+
+	/// True if the read was forcefully ended.
+	pub fn end_reason_forced(mut self, end_reason_forced: bool) -> ReadDataBuilder
+	{
+		self.inner.end_reason_forced = end_reason_forced;
+		self
+	}
+
+	/// Run info index returned by [`crate::writer::Writer::add_run_info`].
+	pub fn run_info(mut self, run_info: i16) -> ReadDataBuilder
+	{
+		self.inner.run_info = run_info;
+		self
+	}
+
+	/// Number of minknow events that the read contains.
+	pub fn num_minknow_events(mut self, num_minknow_events: u64) -> ReadDataBuilder
+	{
+		self.inner.num_minknow_events = num_minknow_events;
+		self
+	}
+
+	/// Tracked scaling scale and shift.
+	pub fn tracked_scaling(mut self, scale: f32, shift: f32) -> ReadDataBuilder
+	{
+		self.inner.tracked_scaling_scale = scale;
+		self.inner.tracked_scaling_shift = shift;
+		self
+	}
+
+	/// Predicted scaling scale and shift.
+	pub fn predicted_scaling(mut self, scale: f32, shift: f32) -> ReadDataBuilder
+	{
+		self.inner.predicted_scaling_scale = scale;
+		self.inner.predicted_scaling_shift = shift;
+		self
+	}
+
+	/// Number of reads since last mux change.
+	pub fn num_reads_since_mux_change(mut self, num_reads_since_mux_change: u32)
+		-> ReadDataBuilder
+	{
+		self.inner.num_reads_since_mux_change = num_reads_since_mux_change;
+		self
+	}
+
+	/// Time in seconds since last mux change.
+	pub fn time_since_mux_change(mut self, time_since_mux_change: f32) -> ReadDataBuilder
+	{
+		self.inner.time_since_mux_change = time_since_mux_change;
+		self
+	}
+
+	/// The uncompressed signal for the read.
+	pub fn signal(mut self, signal: Vec<i16>) -> ReadDataBuilder
+	{
+		self.inner.signal = signal;
+		self
+	}
+
+	/// The signal for the read in picoamps, converted back to raw ADC samples.
+	/// The calibration must be set with [`ReadDataBuilder::calibration`] beforehand.
+	pub fn signal_pa(mut self, signal: &[f32]) -> ReadDataBuilder
+	{
+		let (offset, scale) = (self.inner.calibration_offset, self.inner.calibration_scale);
+		self.inner.signal = signal.iter().map(|sample| pa_to_adc(*sample, offset, scale)).collect();
+		self
+	}
+
+	/// Finish building the read.
+	pub fn build(self) -> ReadData
+	{
+		self.inner
+	}
+}
+
+/// Owned run information used when adding run info to a [`crate::writer::Writer`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunInfoData
+{
+	/// Acquisition id.
+	pub acquisition_id: String,
+	/// Acquisition start time in miliseconds.
+	pub acquisition_start_time_ms: i64,
+	/// Max adc.
+	pub adc_max: i16,
+	/// Min adc.
+	pub adc_min: i16,
+	/// Context data.
+	pub context_tags: HashMap<String, String>,
+	/// Experiment name.
+	pub experiment_name: String,
+	/// Flowcell ID.
+	pub flow_cell_id: String,
+	/// Flowcell product code.
+	pub flow_cell_product_code: String,
+	/// Protocol name.
+	pub protocol_name: String,
+	/// Protocol run id.
+	pub protocol_run_id: String,
+	/// Start time.
+	pub protocol_start_time_ms: i64,
+	/// Sample id.
+	pub sample_id: String,
+	/// The sample rate of the flowcell.
+	pub sample_rate: u16,
+	/// The sequencing kit used.
+	pub sequencing_kit: String,
+	/// Sequencer position.
+	pub sequencer_position: String,
+	/// Sequencer position type.
+	pub sequencer_position_type: String,
+	/// Software used for sequencing.
+	pub software: String,
+	/// System name from the seequencing software.
+	pub system_name: String,
+	/// System type.
+	pub system_type: String,
+	/// Tracking id data.
+	pub tracking_id: HashMap<String, String>,
+}
+
+/// Where a chunk of a read's signal is stored in the signal table, see `Read::signal_rows`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignalRow
+{
+	/// The signal table batch holding the chunk.
+	pub batch_index: usize,
+	/// The row of the chunk within its batch.
+	pub batch_row_index: usize,
+	/// The index of the chunk's first sample within the read's signal.
+	pub start_sample: u64,
+	/// The number of samples stored in the chunk.
+	pub sample_count: u32,
+	/// The number of bytes used to store the chunk, after any compression.
+	pub byte_count: usize,
+}
+
+impl SignalRow
+{
+	/// The ratio of uncompressed to stored size for the chunk.
+	pub fn compression_ratio(&self) -> f64
+	{
+		(self.sample_count as f64 * std::mem::size_of::<i16>() as f64) / self.byte_count as f64
+	}
+}
+
+/// Which requested read ids were found and in which file, see `Reader::fetch_report`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchReport
+{
+	pub(crate) found: Vec<(uuid::Uuid, PathBuf)>,
+	pub(crate) missing: Vec<uuid::Uuid>,
+}
+
+impl FetchReport
+{
+	/// Read ids found in the open files, with the path of the file each was found in.
+	pub fn found(&self) -> &[(uuid::Uuid, PathBuf)]
+	{
+		&self.found
+	}
+
+	/// Read ids not found in any open file.
+	pub fn missing(&self) -> &[uuid::Uuid]
+	{
+		&self.missing
+	}
+
+	/// Returns true when every requested read id was found.
+	pub fn is_complete(&self) -> bool
+	{
+		self.missing.is_empty()
+	}
+}
+
+/// Signal loaded by `Reads::with_signal`.
+/// The buffer is returned to the iterator's pool when dropped.
+pub struct SignalBuffer
+{
+	samples: Vec<i16>,
+	pool: Arc<Mutex<Vec<Vec<i16>>>>,
+}
+
+impl SignalBuffer
+{
+	/// Take a buffer from the pool, or a new one if the pool is empty.
+	pub(crate) fn from_pool(pool: &Arc<Mutex<Vec<Vec<i16>>>>) -> SignalBuffer
+	{
+		SignalBuffer {
+			samples: pool.lock().ok().and_then(|mut pool| pool.pop()).unwrap_or_default(),
+			pool: pool.clone(),
+		}
+	}
+}
+
+impl Deref for SignalBuffer
+{
+	type Target = Vec<i16>;
+
+	fn deref(&self) -> &Vec<i16>
+	{
+		&self.samples
+	}
+}
+
+impl DerefMut for SignalBuffer
+{
+	fn deref_mut(&mut self) -> &mut Vec<i16>
+	{
+		&mut self.samples
+	}
+}
+
+impl Drop for SignalBuffer
+{
+	fn drop(&mut self)
+	{
+		if let Ok(mut pool) = self.pool.lock()
+		{
+			pool.push(std::mem::take(&mut self.samples));
+		}
+	}
+}
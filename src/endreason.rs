@@ -1,25 +1,27 @@
 use std::fmt;
 
+/// Numbered as pod5_end_reason in the pod5 C API.
 #[doc(hidden)]
 #[repr(u32)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum EndReason
 {
-	Unknown = crate::pod5_ffi::pod5_end_reason_POD5_END_REASON_UNKNOWN,
-	MuxChange = crate::pod5_ffi::pod5_end_reason_POD5_END_REASON_MUX_CHANGE,
-	UnblockMuxChange = crate::pod5_ffi::pod5_end_reason_POD5_END_REASON_UNBLOCK_MUX_CHANGE,
-	DataServiceUnblockMuxChange =
-		crate::pod5_ffi::pod5_end_reason_POD5_END_REASON_DATA_SERVICE_UNBLOCK_MUX_CHANGE,
-	SignalPositive = crate::pod5_ffi::pod5_end_reason_POD5_END_REASON_SIGNAL_POSITIVE,
-	SignalNegative = crate::pod5_ffi::pod5_end_reason_POD5_END_REASON_SIGNAL_NEGATIVE,
-	APIRequest = crate::pod5_ffi::pod5_end_reason_POD5_END_REASON_API_REQUEST,
-	DeviceDataError = crate::pod5_ffi::pod5_end_reason_POD5_END_REASON_DEVICE_DATA_ERROR,
-	AnalysisConfigChange = crate::pod5_ffi::pod5_end_reason_POD5_END_REASON_ANALYSIS_CONFIG_CHANGE,
+	#[default]
+	Unknown = 0,
+	MuxChange = 1,
+	UnblockMuxChange = 2,
+	DataServiceUnblockMuxChange = 3,
+	SignalPositive = 4,
+	SignalNegative = 5,
+	APIRequest = 6,
+	DeviceDataError = 7,
+	AnalysisConfigChange = 8,
 }
 
 impl EndReason
 {
+	#[cfg(feature = "ffi")]
 	pub(crate) fn end_reason_from_code(code: i16) -> EndReason
 	{
 		match code
@@ -35,6 +37,24 @@ impl EndReason
 			_ => EndReason::Unknown,
 		}
 	}
+
+	/// Map the end reason names stored in the reads table dictionary.
+	#[cfg(feature = "native")]
+	pub(crate) fn end_reason_from_name(name: &str) -> EndReason
+	{
+		match name
+		{
+			"mux_change" => EndReason::MuxChange,
+			"unblock_mux_change" => EndReason::UnblockMuxChange,
+			"data_service_unblock_mux_change" => EndReason::DataServiceUnblockMuxChange,
+			"signal_positive" => EndReason::SignalPositive,
+			"signal_negative" => EndReason::SignalNegative,
+			"api_request" => EndReason::APIRequest,
+			"device_data_error" => EndReason::DeviceDataError,
+			"analysis_config_change" => EndReason::AnalysisConfigChange,
+			_ => EndReason::Unknown,
+		}
+	}
 }
 
 impl fmt::Display for EndReason
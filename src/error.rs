@@ -1,5 +1,59 @@
+#[cfg(feature = "ffi")]
+use std::cell::Cell;
+#[cfg(feature = "ffi")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
 use thiserror::Error;
 
+/// The pod5 C API keeps the error of the last call in process wide globals, which every call
+/// resets. Calls into the library, along with the error check that follows them, are serialised
+/// through this lock so that threads sharing a reader cannot clobber or observe each other's
+/// errors.
+#[cfg(feature = "ffi")]
+static FFI_LOCK: Mutex<()> = Mutex::new(());
+
+#[cfg(feature = "ffi")]
+thread_local! {
+	static FFI_LOCK_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Held while calling into the pod5 library, see [`ffi_lock`].
+#[cfg(feature = "ffi")]
+pub(crate) struct FfiLock
+{
+	_guard: Option<MutexGuard<'static, ()>>,
+}
+
+/// Serialise calls into the pod5 library until the returned lock is dropped.
+///
+/// The lock is reentrant, so functions holding it can call others that take it again.
+#[cfg(feature = "ffi")]
+pub(crate) fn ffi_lock() -> FfiLock
+{
+	let depth = FFI_LOCK_DEPTH.with(|depth| depth.replace(depth.get() + 1));
+	let guard = if depth == 0
+	{
+		// Nothing guarded by the lock can be left half updated by a panic.
+		Some(FFI_LOCK.lock().unwrap_or_else(PoisonError::into_inner))
+	}
+	else
+	{
+		None
+	};
+
+	FfiLock { _guard: guard }
+}
+
+#[cfg(feature = "ffi")]
+impl Drop for FfiLock
+{
+	fn drop(&mut self)
+	{
+		FFI_LOCK_DEPTH.with(|depth| depth.set(depth.get() - 1));
+	}
+}
+
+#[cfg(feature = "ffi")]
 #[macro_export]
 #[doc(hidden)]
 macro_rules! pod5_check_error {
@@ -21,6 +75,29 @@ macro_rules! pod5_check_error {
 	}};
 }
 
+#[cfg(feature = "ffi")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! pod5_check_result {
+	() => {{
+		unsafe {
+			let error_code = $crate::pod5_ffi::pod5_get_error_no();
+			if error_code != $crate::pod5_ffi::pod5_error_POD5_OK
+			{
+				let c_str = std::ffi::CStr::from_ptr($crate::pod5_ffi::pod5_get_error_string());
+				return Err($crate::error::Error::from_error_code(
+					error_code,
+					c_str
+						.to_str()
+						.unwrap_or("Failed to obtain error message")
+						.to_string(),
+				));
+			}
+		}
+	}};
+}
+
+#[cfg(feature = "ffi")]
 #[macro_export]
 #[doc(hidden)]
 macro_rules! pod5_some {
@@ -37,6 +114,7 @@ macro_rules! pod5_some {
 	}};
 }
 
+#[cfg(feature = "ffi")]
 #[macro_export]
 #[doc(hidden)]
 macro_rules! pod5_ok {
@@ -111,12 +189,20 @@ pub enum Error
 	ArrowIOError(#[from] std::io::Error),
 	#[error("Compression Arrow Error")]
 	ArrowCompressionError(String),
+	#[error("Arrow error: {0}")]
+	ArrowError(#[from] arrow::error::ArrowError),
+	#[cfg(feature = "parquet")]
+	#[error("Parquet error: {0}")]
+	ParquetError(#[from] parquet::errors::ParquetError),
+	#[error("VBZ error: {0}")]
+	VbzError(String),
 	#[error("String conversion error")]
 	StringError(#[from] std::str::Utf8Error),
 }
 
 impl Error
 {
+	#[cfg(feature = "ffi")]
 	pub(crate) fn from_error_code(code: u32, message: String) -> Error
 	{
 		match code
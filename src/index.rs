@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Identifies a decapod read index sidecar file, and its layout version.
+const INDEX_MAGIC: [u8; 8] = *b"DCPIDX02";
+
+/// Size of the sidecar header: magic, file identifier, file stamp and read count.
+const INDEX_HEADER_LEN: usize = 8 + 16 + 8 + 16 + 8;
+
+/// Numbers the temporary files of index writes made by this process.
+static INDEX_WRITES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Where a read is stored within the open pod5 files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ReadLocation
+{
+	pub(crate) file: usize,
+	pub(crate) batch: usize,
+	pub(crate) row: usize,
+}
+
+/// A single open pod5 file that can be indexed, implemented by each reader backend.
+pub(crate) trait IndexSource
+{
+	/// The path the file was opened from.
+	fn path(&self) -> &Path;
+	/// Unique identifier of the file.
+	fn file_identifier(&self) -> crate::error::Result<uuid::Uuid>;
+	/// The number of reads in the file.
+	fn count(&self) -> crate::error::Result<usize>;
+	/// Every read id in the file, in file order.
+	fn read_ids(&self) -> crate::error::Result<Vec<uuid::Uuid>>;
+	/// The row count of every read batch.
+	fn batch_row_counts(&self) -> crate::error::Result<Vec<u64>>;
+}
+
+impl<T: IndexSource> IndexSource for std::sync::Arc<T>
+{
+	fn path(&self) -> &Path
+	{
+		(**self).path()
+	}
+
+	fn file_identifier(&self) -> crate::error::Result<uuid::Uuid>
+	{
+		(**self).file_identifier()
+	}
+
+	fn count(&self) -> crate::error::Result<usize>
+	{
+		(**self).count()
+	}
+
+	fn read_ids(&self) -> crate::error::Result<Vec<uuid::Uuid>>
+	{
+		(**self).read_ids()
+	}
+
+	fn batch_row_counts(&self) -> crate::error::Result<Vec<u64>>
+	{
+		(**self).batch_row_counts()
+	}
+}
+
+/// The size and modification time of a pod5 file, recorded in its sidecar index so an index left
+/// over from before the file was rewritten is rebuilt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp
+{
+	length: u64,
+	modified_ns: u128,
+}
+
+impl FileStamp
+{
+	fn of(path: &Path) -> std::io::Result<FileStamp>
+	{
+		let metadata = std::fs::metadata(path)?;
+		let modified = metadata
+			.modified()?
+			.duration_since(std::time::UNIX_EPOCH)
+			.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+		Ok(FileStamp {
+			length: metadata.len(),
+			modified_ns: modified.as_nanos(),
+		})
+	}
+}
+
+/// Maps read ids to their location within the open pod5 files.
+pub(crate) struct ReadIndex
+{
+	locations: HashMap<uuid::Uuid, ReadLocation>,
+}
+
+impl ReadIndex
+{
+	/// Index every read in the given files, using `.pod5.idx` sidecar files when `index_files`
+	/// is set.
+	pub(crate) fn build<R: IndexSource>(
+		readers: &[R],
+		index_files: bool,
+	) -> crate::error::Result<ReadIndex>
+	{
+		let mut locations = HashMap::new();
+
+		for (file, reader) in readers.iter().enumerate()
+		{
+			let entries = if index_files
+			{
+				cached_file_entries(reader)?
+			}
+			else
+			{
+				file_entries(reader)?
+			};
+
+			for (read_id, batch, row) in entries
+			{
+				// Keep the first location when a read id is found in more than one file.
+				locations.entry(read_id).or_insert(ReadLocation {
+					file,
+					batch: batch as usize,
+					row: row as usize,
+				});
+			}
+		}
+
+		Ok(ReadIndex { locations })
+	}
+
+	pub(crate) fn get(&self, read_id: &uuid::Uuid) -> Option<ReadLocation>
+	{
+		self.locations.get(read_id).copied()
+	}
+}
+
+/// Every read id of a single pod5 file, along with the batch and row it is stored in.
+pub(crate) fn file_entries<R: IndexSource>(
+	reader: &R,
+) -> crate::error::Result<Vec<(uuid::Uuid, u32, u32)>>
+{
+	// Read ids are returned in file order, so batches can be walked alongside them.
+	let mut read_ids = reader.read_ids()?.into_iter();
+	let mut entries = Vec::with_capacity(read_ids.len());
+
+	for (batch, row_count) in reader.batch_row_counts()?.into_iter().enumerate()
+	{
+		for row in 0..row_count as u32
+		{
+			let Some(read_id) = read_ids.next()
+			else
+			{
+				return Err(crate::error::Error::IndexError(format!(
+					"{}: fewer read ids than batch rows",
+					reader.path().display()
+				)));
+			};
+
+			entries.push((read_id, batch as u32, row));
+		}
+	}
+
+	Ok(entries)
+}
+
+/// The sidecar index path for a pod5 file, e.g. `reads.pod5.idx` for `reads.pod5`.
+fn index_path(path: &Path) -> PathBuf
+{
+	let mut index_path = path.as_os_str().to_owned();
+	index_path.push(".idx");
+	PathBuf::from(index_path)
+}
+
+/// Load the entries of a pod5 file from its sidecar index, building and writing the index if it
+/// is missing or no longer matches the file.
+fn cached_file_entries<R: IndexSource>(
+	reader: &R,
+) -> crate::error::Result<Vec<(uuid::Uuid, u32, u32)>>
+{
+	let path = index_path(reader.path());
+	let file_identifier = reader.file_identifier()?;
+
+	// Taken before indexing, so a file changed while it is indexed is indexed again next time.
+	let Ok(stamp) = FileStamp::of(reader.path())
+	else
+	{
+		return file_entries(reader);
+	};
+
+	if let Ok(entries) = read_index_file(&path, &file_identifier, &stamp, reader.count()?)
+	{
+		return Ok(entries);
+	}
+
+	let entries = file_entries(reader)?;
+
+	// The sidecar is only a cache, so a read only directory falls back to indexing in memory.
+	let _ = write_index_file(&path, &file_identifier, &stamp, &entries);
+
+	Ok(entries)
+}
+
+/// Read a sidecar index, failing if it was not built from a pod5 file with the given file
+/// identifier, size, modification time and read count.
+fn read_index_file(
+	path: &Path,
+	file_identifier: &uuid::Uuid,
+	stamp: &FileStamp,
+	read_count: usize,
+) -> std::io::Result<Vec<(uuid::Uuid, u32, u32)>>
+{
+	let mut file = BufReader::new(File::open(path)?);
+
+	let mut header = [0u8; INDEX_HEADER_LEN];
+	file.read_exact(&mut header)?;
+
+	let indexed_stamp = FileStamp {
+		length: u64::from_le_bytes(header[24..32].try_into().unwrap()),
+		modified_ns: u128::from_le_bytes(header[32..48].try_into().unwrap()),
+	};
+	let count = u64::from_le_bytes(header[48..56].try_into().unwrap()) as usize;
+	if header[..8] != INDEX_MAGIC
+		|| header[8..24] != file_identifier.as_bytes()[..]
+		|| indexed_stamp != *stamp
+		|| count != read_count
+	{
+		return Err(std::io::Error::new(
+			std::io::ErrorKind::InvalidData,
+			"index does not match pod5 file",
+		));
+	}
+
+	let mut entries = Vec::with_capacity(count);
+	let mut entry = [0u8; 24];
+
+	for _ in 0..count
+	{
+		file.read_exact(&mut entry)?;
+		entries.push((
+			uuid::Uuid::from_bytes(entry[..16].try_into().unwrap()),
+			u32::from_le_bytes(entry[16..20].try_into().unwrap()),
+			u32::from_le_bytes(entry[20..24].try_into().unwrap()),
+		));
+	}
+
+	Ok(entries)
+}
+
+/// Write a sidecar index, holding the file identifier, size and modification time of the pod5
+/// file it was built from, followed by the read id, batch and row of every read.
+///
+/// The index is written to a temporary file alongside it and renamed into place, so a reader
+/// never sees a partly written index. Each write uses its own temporary file, so threads or
+/// processes indexing the same pod5 file at once don't write over each other.
+fn write_index_file(
+	path: &Path,
+	file_identifier: &uuid::Uuid,
+	stamp: &FileStamp,
+	entries: &[(uuid::Uuid, u32, u32)],
+) -> std::io::Result<()>
+{
+	let mut temp_path = path.as_os_str().to_owned();
+	temp_path.push(format!(
+		".{}.{}.tmp",
+		std::process::id(),
+		INDEX_WRITES.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+	));
+	let temp_path = PathBuf::from(temp_path);
+
+	let result = write_index_entries(&temp_path, file_identifier, stamp, entries)
+		.and_then(|_| std::fs::rename(&temp_path, path));
+
+	if result.is_err()
+	{
+		let _ = std::fs::remove_file(&temp_path);
+	}
+
+	result
+}
+
+fn write_index_entries(
+	path: &Path,
+	file_identifier: &uuid::Uuid,
+	stamp: &FileStamp,
+	entries: &[(uuid::Uuid, u32, u32)],
+) -> std::io::Result<()>
+{
+	let mut file = BufWriter::new(File::create(path)?);
+
+	file.write_all(&INDEX_MAGIC)?;
+	file.write_all(file_identifier.as_bytes())?;
+	file.write_all(&stamp.length.to_le_bytes())?;
+	file.write_all(&stamp.modified_ns.to_le_bytes())?;
+	file.write_all(&(entries.len() as u64).to_le_bytes())?;
+
+	for (read_id, batch, row) in entries
+	{
+		file.write_all(read_id.as_bytes())?;
+		file.write_all(&batch.to_le_bytes())?;
+		file.write_all(&row.to_le_bytes())?;
+	}
+
+	file.flush()
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	/// An in memory file, written out to `path` so the sidecar can be stamped with it.
+	struct TestSource
+	{
+		path: PathBuf,
+		read_ids: Vec<uuid::Uuid>,
+	}
+
+	impl IndexSource for TestSource
+	{
+		fn path(&self) -> &Path
+		{
+			&self.path
+		}
+
+		fn file_identifier(&self) -> crate::error::Result<uuid::Uuid>
+		{
+			Ok(uuid::Uuid::from_u128(1))
+		}
+
+		fn count(&self) -> crate::error::Result<usize>
+		{
+			Ok(self.read_ids.len())
+		}
+
+		fn read_ids(&self) -> crate::error::Result<Vec<uuid::Uuid>>
+		{
+			Ok(self.read_ids.clone())
+		}
+
+		fn batch_row_counts(&self) -> crate::error::Result<Vec<u64>>
+		{
+			Ok(vec![self.read_ids.len() as u64])
+		}
+	}
+
+	fn test_dir(name: &str) -> PathBuf
+	{
+		let dir = std::env::temp_dir().join(format!("decapod-{}-{}", name, std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		dir
+	}
+
+	#[test]
+	fn rewritten_file_is_reindexed()
+	{
+		let dir = test_dir("reindex");
+		let mut source = TestSource {
+			path: dir.join("reads.pod5"),
+			read_ids: (0..3).map(uuid::Uuid::from_u128).collect(),
+		};
+
+		std::fs::write(&source.path, b"first").unwrap();
+		let index = ReadIndex::build(std::slice::from_ref(&source), true).unwrap();
+		assert!(index_path(&source.path).exists());
+		assert!(index.get(&uuid::Uuid::from_u128(2)).is_some());
+
+		// Same identifier and read count, so only the file stamp tells the two apart.
+		source.read_ids = (10..13).map(uuid::Uuid::from_u128).collect();
+		std::fs::write(&source.path, b"rewritten").unwrap();
+		let index = ReadIndex::build(std::slice::from_ref(&source), true).unwrap();
+		assert!(index.get(&uuid::Uuid::from_u128(2)).is_none());
+		assert_eq!(
+			index.get(&uuid::Uuid::from_u128(12)),
+			Some(ReadLocation { file: 0, batch: 0, row: 2 })
+		);
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn concurrent_index_writes()
+	{
+		let dir = test_dir("concurrent");
+		let path = dir.join("reads.pod5.idx");
+		let file_identifier = uuid::Uuid::from_u128(1);
+		let stamp = FileStamp {
+			length: 1,
+			modified_ns: 1,
+		};
+		let entries: Vec<(uuid::Uuid, u32, u32)> =
+			(0..10_000).map(|row| (uuid::Uuid::from_u128(row as u128), 0, row)).collect();
+
+		std::thread::scope(|scope| {
+			for _ in 0..8
+			{
+				scope.spawn(|| write_index_file(&path, &file_identifier, &stamp, &entries).unwrap());
+			}
+		});
+
+		assert_eq!(read_index_file(&path, &file_identifier, &stamp, entries.len()).unwrap(), entries);
+		assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+}
@@ -55,18 +55,72 @@
 //!     Ok(())
 //! }
 //! ```
+//! Create a new pod5 file and write a read to it.
+//!
+//! ```
+//! use decapod::writer::Writer;
+//! use decapod::reader::ReadData;
+//! use decapod::runinfo::RunInfoData;
+//! use std::error::Error;
+//!
+//! fn main() -> Result<(), Box<dyn Error>>
+//! {
+//!     let mut writer = Writer::from_path("output.pod5", None)?;
+//!
+//!     let pore_type = writer.add_pore("not_set")?;
+//!     let run_info = writer.add_run_info(&RunInfoData::default())?;
+//!
+//!     writer.add_read(&ReadData {
+//!         read_id: uuid::Uuid::new_v4(),
+//!         pore_type,
+//!         run_info,
+//!         signal: vec![0i16; 100],
+//!         ..Default::default()
+//!     })?;
+//!
+//!     writer.close()?;
+//!     Ok(())
+//! }
+//! ```
 
+#[cfg(any(feature = "ffi", feature = "native"))]
+mod data;
 /// end reason metadata read from pod5 files.
 pub mod endreason;
 /// pod5 error codes.
 pub mod error;
 /// Read file info metadata stored within pod5 files.
+#[cfg(feature = "ffi")]
 pub mod fileinfo;
+#[cfg(any(feature = "ffi", feature = "native"))]
+mod index;
+/// Merge multiple pod5 files into a single pod5 file.
+#[cfg(feature = "ffi")]
+pub mod merge;
+/// Read pod5 files without the pod5 C++ library (enabled with the 'native' feature).
+#[cfg(feature = "native")]
+pub mod native;
+#[cfg(feature = "ffi")]
 mod pod5_ffi;
+#[cfg(feature = "ffi")]
 mod read;
+#[cfg(feature = "ffi")]
 mod readbatch;
 /// Open and read pod5 files.
+#[cfg(feature = "ffi")]
 pub mod reader;
+#[cfg(feature = "ffi")]
 mod reads;
 /// obtain additional metadata stored within pod5 files.
+#[cfg(feature = "ffi")]
 pub mod runinfo;
+#[cfg(any(feature = "ffi", feature = "native"))]
+mod signaltable;
+/// Split reads from pod5 files into multiple new pod5 files.
+#[cfg(feature = "ffi")]
+pub mod subset;
+/// Pure Rust VBZ signal compression, compatible with pod5 files.
+pub mod vbz;
+/// Create and write pod5 files.
+#[cfg(feature = "ffi")]
+pub mod writer;
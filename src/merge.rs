@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::reader::Reader;
+use crate::writer::{IndexRemap, ReadSource, Writer, WriterOptions};
+
+/// How [`merge`] handles a read id that has already been written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateReads
+{
+	/// Write every read, without checking for duplicate read ids.
+	#[default]
+	Keep,
+	/// Only write the first read seen with each read id.
+	Skip,
+	/// Stop merging, remove the merged file and return an error.
+	Error,
+}
+
+/// Merge every read from all files open in `reader` into a single new pod5 file.
+///
+/// Run info shared between files is only written once, identified by acquisition id, and
+/// each read's run info index is remapped to the merged file. Signal is copied without
+/// being recompressed.
+///
+/// If merging fails, including on a duplicate read id with [`DuplicateReads::Error`], the
+/// partly written file is removed.
+///
+/// # Arguments
+///
+/// * `reader` - The reader to take reads from, typically opened with [`Reader::from_vec`].
+/// * `path` - The path of the merged pod5 file to create.
+/// * `duplicates` - How to handle read ids found in more than one file, see [`DuplicateReads`].
+/// * `options` - the [`WriterOptions`] object. Pass None to use the defaults (typical).
+///
+/// # Example
+///
+/// ```
+/// let reader = Reader::from_vec(vec!["sample1.pod5", "sample2.pod5"], None)?;
+/// let count = merge(&reader, "merged.pod5", DuplicateReads::Skip, None)?;
+/// ```
+///
+/// # Returns
+///
+/// The number of reads written to the merged file.
+pub fn merge<P, W>(
+	reader: &Reader,
+	path: P,
+	duplicates: DuplicateReads,
+	options: W,
+) -> crate::error::Result<usize>
+where
+	P: AsRef<Path>,
+	W: Into<Option<WriterOptions>>,
+{
+	let path = path.as_ref();
+	let mut writer = Writer::from_path(path, options)?;
+
+	match merge_reads(reader, &mut writer, duplicates)
+	{
+		Ok(count) =>
+		{
+			writer.close()?;
+			Ok(count)
+		}
+		Err(e) =>
+		{
+			// Don't leave a partly merged file behind.
+			drop(writer);
+			let _ = std::fs::remove_file(path);
+
+			Err(e)
+		}
+	}
+}
+
+fn merge_reads(
+	reader: &Reader,
+	writer: &mut Writer,
+	duplicates: DuplicateReads,
+) -> crate::error::Result<usize>
+{
+	let mut seen: HashSet<uuid::Uuid> = HashSet::new();
+	let mut count = 0;
+
+	for internal_reader in reader.inner.iter()
+	{
+		let mut source = ReadSource::new(internal_reader)?;
+		let mut remap = IndexRemap::default();
+		let reads = crate::reads::Reads::new(std::slice::from_ref(internal_reader), None);
+
+		for read in reads
+		{
+			let read = read?;
+
+			if duplicates != DuplicateReads::Keep && !seen.insert(read.uuid())
+			{
+				if duplicates == DuplicateReads::Error
+				{
+					return Err(crate::error::Error::KeyError(format!(
+						"duplicate read id: {}",
+						read.uuid()
+					)));
+				}
+
+				continue;
+			}
+
+			writer.copy_read(&mut source, &mut remap, &read)?;
+			count += 1;
+		}
+	}
+
+	Ok(count)
+}
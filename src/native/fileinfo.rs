@@ -0,0 +1,57 @@
+/// file identifier metadata within the pod5 file being read.
+/// # Example
+/// ````
+/// let reader = Reader::from_path("sample.pod", None);
+/// for fileinfo in reader.info().iter()
+/// {
+///     let fileinfo = fileinfo?;
+///     println!("{}", fileinfo.file_identifier());
+/// }
+/// ````
+pub struct FileInfo
+{
+	pub(crate) file_identifier: uuid::Uuid,
+	pub(crate) version: [u16; 3],
+}
+
+impl FileInfo
+{
+	pub(crate) fn new(footer: &super::Footer) -> FileInfo
+	{
+		// The version is stored as text, e.g. "0.3.10".
+		let mut version = [0u16; 3];
+		for (part, value) in footer.pod5_version.split('.').zip(version.iter_mut())
+		{
+			*value = part.parse().unwrap_or(0);
+		}
+
+		FileInfo {
+			file_identifier: footer.file_identifier,
+			version,
+		}
+	}
+
+	/// Obtain the uuid of the file identifier of the pod5.
+	pub fn file_identifier(&self) -> uuid::Uuid
+	{
+		self.file_identifier
+	}
+
+	/// The major version of the pod5 file.
+	pub fn major_version(&self) -> u16
+	{
+		self.version[0]
+	}
+
+	/// The minor version of the pod5 file.
+	pub fn minor_version(&self) -> u16
+	{
+		self.version[1]
+	}
+
+	/// The revision version of the pod5 file.
+	pub fn revision_version(&self) -> u16
+	{
+		self.version[2]
+	}
+}
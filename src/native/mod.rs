@@ -0,0 +1,1007 @@
+//! The file footer and embedded Arrow tables are parsed directly. This currently covers the
+//! V3 table layout. This module sits alongside the C++ backend, so both can be enabled at once.
+//! Building with `--no-default-features --features native` leaves out the default 'ffi'
+//! feature, and with it the C++ library.
+//!
+//! The native [`reader::Reader`] covers opening files, read ids, file and run info, read
+//! iteration and lookup, and signal. Writing, subsetting, merging, dataframe and parquet export
+//! still need the C++ library, so are only available with the 'ffi' feature.
+//!
+//! # Example
+//!
+//! ```
+//! let reader = NativeReader::from_path("sample.pod5")?;
+//! for read in reader.reads()?
+//! {
+//!     let signal = reader.signal(&read)?;
+//!     println!("{} has {} samples", read.read_id, signal.len());
+//! }
+//! ```
+
+use std::fs::File;
+use std::io::{BufReader, Read as StdRead, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use arrow::array::{Array, ArrowPrimitiveType, AsArray, PrimitiveArray, StringArray};
+use arrow::datatypes::{
+	Float32Type, Int16Type, TimestampMillisecondType, UInt16Type, UInt32Type, UInt64Type,
+	UInt8Type,
+};
+use arrow::ipc::reader::FileReader;
+use arrow::record_batch::RecordBatch;
+
+use crate::data::RunInfoData;
+use crate::signaltable::{EmbeddedFile, SignalTable};
+
+/// File info metadata read without the pod5 C++ library.
+pub mod fileinfo;
+mod read;
+/// Open and read pod5 files without the pod5 C++ library.
+pub mod reader;
+mod reads;
+/// Run info metadata read without the pod5 C++ library.
+pub mod runinfo;
+
+/// The signature found at the start and end of every pod5 file.
+const SIGNATURE: [u8; 8] = [0x8b, b'P', b'O', b'D', b'\r', b'\n', 0x1a, b'\n'];
+const SECTION_MARKER_LENGTH: u64 = 16;
+
+fn format_error(message: &str) -> crate::error::Error
+{
+	crate::error::Error::InvalidError(format!("malformed pod5 file: {}", message))
+}
+
+/// The kind of table held by an embedded file, see [`Footer::contents`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentType
+{
+	/// An index not known to this version of the format.
+	OtherIndex,
+	/// The reads table.
+	ReadsTable,
+	/// The signal table.
+	SignalTable,
+	/// The read id index.
+	ReadIdIndex,
+	/// The run info table.
+	RunInfoTable,
+}
+
+impl ContentType
+{
+	/// Map the footer's content type, numbered as in pod5's footer.fbs. An absent field means
+	/// the flatbuffer default of 0, the reads table.
+	fn from_code(code: i16) -> ContentType
+	{
+		match code
+		{
+			0 => ContentType::ReadsTable,
+			1 => ContentType::SignalTable,
+			2 => ContentType::ReadIdIndex,
+			4 => ContentType::RunInfoTable,
+			_ => ContentType::OtherIndex,
+		}
+	}
+}
+
+/// The location of an Arrow file embedded within a pod5 file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbeddedTable
+{
+	/// Offset of the table from the start of the pod5 file.
+	pub offset: u64,
+	/// Length of the table in bytes.
+	pub length: u64,
+	/// What the table holds.
+	pub content_type: ContentType,
+}
+
+/// The footer stored at the end of a pod5 file.
+#[derive(Debug, Clone)]
+pub struct Footer
+{
+	/// Unique identifier of the file.
+	pub file_identifier: uuid::Uuid,
+	/// The software that wrote the file.
+	pub software: String,
+	/// The pod5 format version of the file.
+	pub pod5_version: String,
+	/// Every table embedded within the file.
+	pub contents: Vec<EmbeddedTable>,
+}
+
+impl Footer
+{
+	/// Parse the footer flatbuffer.
+	fn parse(data: &[u8]) -> crate::error::Result<Footer>
+	{
+		let footer = FlatTable::root(data)?;
+
+		let file_identifier = footer.string(0)?.unwrap_or_default();
+		let file_identifier = uuid::Uuid::parse_str(file_identifier)
+			.map_err(|_| format_error("invalid file identifier"))?;
+
+		let contents = footer
+			.tables(3)?
+			.iter()
+			.map(|table| {
+				Ok(EmbeddedTable {
+					offset: table.i64(0)?.unwrap_or(0) as u64,
+					length: table.i64(1)?.unwrap_or(0) as u64,
+					content_type: ContentType::from_code(table.i16(3)?.unwrap_or(0)),
+				})
+			})
+			.collect::<crate::error::Result<Vec<EmbeddedTable>>>()?;
+
+		Ok(Footer {
+			file_identifier,
+			software: footer.string(1)?.unwrap_or_default().to_string(),
+			pod5_version: footer.string(2)?.unwrap_or_default().to_string(),
+			contents,
+		})
+	}
+}
+
+/// Minimal reader for the flatbuffer tables used by the footer.
+struct FlatTable<'a>
+{
+	data: &'a [u8],
+	position: usize,
+	vtable: usize,
+}
+
+impl<'a> FlatTable<'a>
+{
+	fn root(data: &'a [u8]) -> crate::error::Result<FlatTable<'a>>
+	{
+		Self::at(data, read_u32(data, 0)? as usize)
+	}
+
+	fn at(data: &'a [u8], position: usize) -> crate::error::Result<FlatTable<'a>>
+	{
+		let vtable = position as i64 - read_i32(data, position)? as i64;
+		if vtable < 0
+		{
+			return Err(format_error("footer vtable out of range"));
+		}
+
+		Ok(FlatTable {
+			data,
+			position,
+			vtable: vtable as usize,
+		})
+	}
+
+	fn field(&self, index: usize) -> crate::error::Result<Option<usize>>
+	{
+		let vtable_size = read_u16(self.data, self.vtable)? as usize;
+		let entry = 4 + 2 * index;
+		if entry + 2 > vtable_size
+		{
+			return Ok(None);
+		}
+
+		Ok(match read_u16(self.data, self.vtable + entry)?
+		{
+			0 => None,
+			offset => Some(self.position + offset as usize),
+		})
+	}
+
+	fn i16(&self, index: usize) -> crate::error::Result<Option<i16>>
+	{
+		self.field(index)?
+			.map(|position| Ok(i16::from_le_bytes(read_bytes(self.data, position)?)))
+			.transpose()
+	}
+
+	fn i64(&self, index: usize) -> crate::error::Result<Option<i64>>
+	{
+		self.field(index)?
+			.map(|position| Ok(i64::from_le_bytes(read_bytes(self.data, position)?)))
+			.transpose()
+	}
+
+	fn string(&self, index: usize) -> crate::error::Result<Option<&'a str>>
+	{
+		let Some(position) = self.field(index)?
+		else
+		{
+			return Ok(None);
+		};
+
+		let start = position + read_u32(self.data, position)? as usize;
+		let length = read_u32(self.data, start)? as usize;
+		let bytes = self
+			.data
+			.get(start + 4..start + 4 + length)
+			.ok_or_else(|| format_error("footer string out of range"))?;
+
+		Ok(Some(std::str::from_utf8(bytes)?))
+	}
+
+	fn tables(&self, index: usize) -> crate::error::Result<Vec<FlatTable<'a>>>
+	{
+		let Some(position) = self.field(index)?
+		else
+		{
+			return Ok(Vec::new());
+		};
+
+		let start = position + read_u32(self.data, position)? as usize;
+		let length = read_u32(self.data, start)? as usize;
+
+		(0..length)
+			.map(|element| {
+				let element = start + 4 + element * 4;
+				Self::at(self.data, element + read_u32(self.data, element)? as usize)
+			})
+			.collect()
+	}
+}
+
+fn read_bytes<const N: usize>(data: &[u8], position: usize) -> crate::error::Result<[u8; N]>
+{
+	data.get(position..position + N)
+		.and_then(|bytes| bytes.try_into().ok())
+		.ok_or_else(|| format_error("footer offset out of range"))
+}
+
+fn read_u16(data: &[u8], position: usize) -> crate::error::Result<u16>
+{
+	Ok(u16::from_le_bytes(read_bytes(data, position)?))
+}
+
+fn read_u32(data: &[u8], position: usize) -> crate::error::Result<u32>
+{
+	Ok(u32::from_le_bytes(read_bytes(data, position)?))
+}
+
+fn read_i32(data: &[u8], position: usize) -> crate::error::Result<i32>
+{
+	Ok(i32::from_le_bytes(read_bytes(data, position)?))
+}
+
+/// Read metadata loaded by [`NativeReader::reads`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Default)]
+pub struct NativeRead
+{
+	/// The read id.
+	pub read_id: uuid::Uuid,
+	/// The read number.
+	pub read_number: u32,
+	/// The first sample of the read.
+	pub start_sample: u64,
+	/// The median before level.
+	pub median_before: f32,
+	/// The channel the read was sequenced on.
+	pub channel: u16,
+	/// The well the read was sequenced on.
+	pub well: u8,
+	/// The pore type.
+	pub pore_type: String,
+	/// The pore type's index within the reads table dictionary.
+	pub pore_type_index: i16,
+	/// Calibration offset.
+	pub calibration_offset: f32,
+	/// Calibration scale.
+	pub calibration_scale: f32,
+	/// The end reason.
+	pub end_reason: String,
+	/// True if the read was forcefully ended.
+	pub end_reason_forced: bool,
+	/// The acquisition id of the read's run info.
+	pub run_info: String,
+	/// The run info's index within the reads table dictionary.
+	pub run_info_index: i16,
+	/// Number of minknow events that the read contains.
+	pub num_minknow_events: u64,
+	/// Tracked scaling scale.
+	pub tracked_scaling_scale: f32,
+	/// Tracked scaling shift.
+	pub tracked_scaling_shift: f32,
+	/// Predicted scaling scale.
+	pub predicted_scaling_scale: f32,
+	/// Predicted scaling shift.
+	pub predicted_scaling_shift: f32,
+	/// Number of reads since last mux change.
+	pub num_reads_since_mux_change: u32,
+	/// Time in seconds since last mux change.
+	pub time_since_mux_change: f32,
+	/// Number of signal samples.
+	pub num_samples: u64,
+	/// The signal table rows holding the read's signal, in sample order.
+	pub signal_rows: Vec<u64>,
+}
+
+/// Reader for a single pod5 file that doesn't use the pod5 C++ library.
+pub struct NativeReader
+{
+	path: PathBuf,
+	footer: Footer,
+	batch_row_counts: OnceLock<Vec<u64>>,
+	run_infos: OnceLock<Vec<RunInfoData>>,
+	/// Signal tables not in use by any thread, so signal can be decoded on several at once.
+	signal_tables: Mutex<Vec<SignalTable>>,
+}
+
+impl NativeReader
+{
+	/// Open a pod5 file and parse its footer.
+	pub fn from_path<P: AsRef<Path>>(path: P) -> crate::error::Result<NativeReader>
+	{
+		let mut file = File::open(&path)?;
+		let file_length = file.metadata()?.len();
+
+		let mut signature = [0u8; 8];
+		file.read_exact(&mut signature)?;
+		if signature != SIGNATURE
+		{
+			return Err(format_error("missing pod5 signature"));
+		}
+
+		// The footer is followed by its length, a section marker and the signature.
+		let trailer_length = 8 + SECTION_MARKER_LENGTH + SIGNATURE.len() as u64;
+		if file_length < SIGNATURE.len() as u64 + trailer_length
+		{
+			return Err(format_error("file too short"));
+		}
+
+		file.seek(SeekFrom::End(-(trailer_length as i64)))?;
+		let mut footer_length = [0u8; 8];
+		file.read_exact(&mut footer_length)?;
+		let footer_length = i64::from_le_bytes(footer_length);
+
+		let footer_end = file_length - trailer_length;
+		if footer_length < 0 || footer_length as u64 > footer_end
+		{
+			return Err(format_error("invalid footer length"));
+		}
+
+		file.seek(SeekFrom::Start(footer_end - footer_length as u64))?;
+		let mut footer = vec![0u8; footer_length as usize];
+		file.read_exact(&mut footer)?;
+
+		Ok(NativeReader {
+			path: path.as_ref().to_path_buf(),
+			footer: Footer::parse(&footer)?,
+			batch_row_counts: OnceLock::new(),
+			run_infos: OnceLock::new(),
+			signal_tables: Mutex::new(Vec::new()),
+		})
+	}
+
+	/// The path the file was opened from.
+	pub fn path(&self) -> &Path
+	{
+		&self.path
+	}
+
+	/// The parsed file footer.
+	pub fn footer(&self) -> &Footer
+	{
+		&self.footer
+	}
+
+	/// Unique identifier of the file.
+	pub fn file_identifier(&self) -> uuid::Uuid
+	{
+		self.footer.file_identifier
+	}
+
+	fn table_location(&self, content_type: ContentType) -> crate::error::Result<&EmbeddedTable>
+	{
+		self.footer
+			.contents
+			.iter()
+			.find(|table| table.content_type == content_type)
+			.ok_or_else(|| format_error(&format!("no {:?} found", content_type)))
+	}
+
+	fn table_file(&self, content_type: ContentType) -> crate::error::Result<EmbeddedFile>
+	{
+		let location = self.table_location(content_type)?;
+		EmbeddedFile::open(&self.path, location.offset, location.length)
+	}
+
+	fn table_batches(&self, content_type: ContentType) -> crate::error::Result<Vec<RecordBatch>>
+	{
+		let reader = FileReader::try_new_buffered(self.table_file(content_type)?, None)?;
+
+		Ok(reader.collect::<Result<Vec<RecordBatch>, arrow::error::ArrowError>>()?)
+	}
+
+	/// An arrow reader over the record batches of the reads table.
+	pub(crate) fn reads_table_reader(
+		&self,
+	) -> crate::error::Result<FileReader<BufReader<EmbeddedFile>>>
+	{
+		Ok(FileReader::try_new_buffered(self.table_file(ContentType::ReadsTable)?, None)?)
+	}
+
+	/// Every record batch of the reads table.
+	pub fn reads_table(&self) -> crate::error::Result<Vec<RecordBatch>>
+	{
+		self.table_batches(ContentType::ReadsTable)
+	}
+
+	/// Every record batch of the run info table.
+	pub fn run_info_table(&self) -> crate::error::Result<Vec<RecordBatch>>
+	{
+		self.table_batches(ContentType::RunInfoTable)
+	}
+
+	/// The row count of every reads table batch, read from the batch metadata on first use.
+	pub(crate) fn reads_batch_row_counts(&self) -> crate::error::Result<&[u64]>
+	{
+		if self.batch_row_counts.get().is_none()
+		{
+			let batch_row_counts =
+				crate::signaltable::batch_row_counts(&mut self.table_file(ContentType::ReadsTable)?)?;
+			let _ = self.batch_row_counts.set(batch_row_counts);
+		}
+
+		Ok(self.batch_row_counts.get().unwrap())
+	}
+
+	/// The total number of reads in the file.
+	pub fn count(&self) -> crate::error::Result<usize>
+	{
+		Ok(self.reads_batch_row_counts()?.iter().sum::<u64>() as usize)
+	}
+
+	/// The read id of every read in the file. Only the read id column is loaded.
+	pub fn read_ids(&self) -> crate::error::Result<Vec<uuid::Uuid>>
+	{
+		let column_index = self.reads_table_reader()?.schema().index_of("read_id")?;
+		let reader = FileReader::try_new_buffered(
+			self.table_file(ContentType::ReadsTable)?,
+			Some(vec![column_index]),
+		)?;
+
+		let mut read_ids = Vec::new();
+		for batch in reader
+		{
+			let batch = batch?;
+			let column = column(&batch, "read_id")?
+				.as_fixed_size_binary_opt()
+				.ok_or_else(|| column_error("read_id"))?;
+
+			for row in 0..column.len()
+			{
+				read_ids.push(
+					uuid::Uuid::from_slice(column.value(row)).map_err(|_| column_error("read_id"))?,
+				);
+			}
+		}
+
+		Ok(read_ids)
+	}
+
+	/// Load the metadata for every read in the file.
+	pub fn reads(&self) -> crate::error::Result<Vec<NativeRead>>
+	{
+		let mut reads = Vec::new();
+		for batch in self.reads_table_reader()?
+		{
+			reads.extend(batch_reads(&batch?)?);
+		}
+
+		Ok(reads)
+	}
+
+	/// Load the metadata for the reads in a single reads table batch.
+	pub(crate) fn reads_batch(&self, batch_index: usize) -> crate::error::Result<Vec<NativeRead>>
+	{
+		let mut reader = self.reads_table_reader()?;
+		reader.set_index(batch_index)?;
+		let batch = reader.next().ok_or_else(|| {
+			crate::error::Error::IndexError(format!("read batch {} not found", batch_index))
+		})??;
+
+		batch_reads(&batch)
+	}
+
+	/// Every run info in the file, in run info table order. The table is loaded on first use.
+	pub fn run_infos(&self) -> crate::error::Result<&[RunInfoData]>
+	{
+		if self.run_infos.get().is_none()
+		{
+			let mut run_infos = Vec::new();
+			for batch in self.run_info_table()?
+			{
+				run_infos.extend(batch_run_infos(&batch)?);
+			}
+			let _ = self.run_infos.set(run_infos);
+		}
+
+		Ok(self.run_infos.get().unwrap())
+	}
+
+	/// The uncompressed signal for a read, decoded with [`crate::vbz`] where compressed.
+	pub fn signal(&self, read: &NativeRead) -> crate::error::Result<Vec<i16>>
+	{
+		let mut signal = Vec::with_capacity(read.num_samples as usize);
+		self.signal_chunks(read, |samples| {
+			signal.extend_from_slice(samples);
+			Ok(())
+		})?;
+
+		Ok(signal)
+	}
+
+	/// Decode each signal row of a read in sample order, passing the samples to `chunk`.
+	pub(crate) fn signal_chunks<F>(&self, read: &NativeRead, mut chunk: F) -> crate::error::Result<()>
+	where
+		F: FnMut(&[i16]) -> crate::error::Result<()>,
+	{
+		self.with_signal_table(|signal_table| {
+			for row in read.signal_rows.iter()
+			{
+				let (batch_index, batch_row) = signal_table.locate(*row)?;
+				chunk(&signal_table.samples(batch_index, batch_row)?)?;
+			}
+
+			Ok(())
+		})
+	}
+
+	/// Describe each signal row of a read, in sample order.
+	pub(crate) fn signal_rows(&self, read: &NativeRead) -> crate::error::Result<Vec<crate::data::SignalRow>>
+	{
+		self.with_signal_table(|signal_table| {
+			let mut start_sample = 0;
+
+			read.signal_rows
+				.iter()
+				.map(|row| {
+					let (batch_index, batch_row_index) = signal_table.locate(*row)?;
+					let sample_count = signal_table.sample_count(batch_index, batch_row_index)?;
+					let signal_row = crate::data::SignalRow {
+						batch_index,
+						batch_row_index,
+						start_sample,
+						sample_count,
+						byte_count: signal_table.byte_count(batch_index, batch_row_index)?,
+					};
+					start_sample += sample_count as u64;
+
+					Ok(signal_row)
+				})
+				.collect()
+		})
+	}
+
+	/// The uncompressed signal for a range of samples within a read, decoding only the signal
+	/// rows overlapping the range.
+	pub(crate) fn signal_range(
+		&self,
+		read: &NativeRead,
+		range: std::ops::Range<usize>,
+	) -> crate::error::Result<Vec<i16>>
+	{
+		let mut signal = Vec::with_capacity(range.len());
+		if range.is_empty()
+		{
+			return Ok(signal);
+		}
+
+		self.with_signal_table(|signal_table| {
+			let mut chunk_start = 0;
+
+			for row in read.signal_rows.iter()
+			{
+				let (batch_index, batch_row) = signal_table.locate(*row)?;
+				let chunk_end = chunk_start + signal_table.sample_count(batch_index, batch_row)? as usize;
+
+				if chunk_end > range.start
+				{
+					let chunk = signal_table.samples(batch_index, batch_row)?;
+					let from = range.start.saturating_sub(chunk_start);
+					let to = range.end.min(chunk_end) - chunk_start;
+					signal.extend_from_slice(&chunk[from..to]);
+				}
+
+				if chunk_end >= range.end
+				{
+					break;
+				}
+
+				chunk_start = chunk_end;
+			}
+
+			Ok(())
+		})?;
+
+		Ok(signal)
+	}
+
+	/// Run `f` with a signal table of this file, reusing one opened by an earlier call where
+	/// one is free.
+	fn with_signal_table<T, F>(&self, f: F) -> crate::error::Result<T>
+	where
+		F: FnOnce(&mut SignalTable) -> crate::error::Result<T>,
+	{
+		let free_table = self
+			.signal_tables
+			.lock()
+			.map_err(|e| crate::error::Error::UnknownError(e.to_string()))?
+			.pop();
+
+		let mut signal_table = match free_table
+		{
+			Some(signal_table) => signal_table,
+			None =>
+			{
+				let location = *self.table_location(ContentType::SignalTable)?;
+				SignalTable::open_at(&self.path, location.offset, location.length)?
+			}
+		};
+
+		let result = f(&mut signal_table);
+
+		if let Ok(mut signal_tables) = self.signal_tables.lock()
+		{
+			signal_tables.push(signal_table);
+		}
+
+		result
+	}
+}
+
+impl crate::index::IndexSource for NativeReader
+{
+	fn path(&self) -> &Path
+	{
+		&self.path
+	}
+
+	fn file_identifier(&self) -> crate::error::Result<uuid::Uuid>
+	{
+		Ok(self.footer.file_identifier)
+	}
+
+	fn count(&self) -> crate::error::Result<usize>
+	{
+		NativeReader::count(self)
+	}
+
+	fn read_ids(&self) -> crate::error::Result<Vec<uuid::Uuid>>
+	{
+		NativeReader::read_ids(self)
+	}
+
+	fn batch_row_counts(&self) -> crate::error::Result<Vec<u64>>
+	{
+		Ok(self.reads_batch_row_counts()?.to_vec())
+	}
+}
+
+/// Load the metadata for every read in a reads table batch.
+fn batch_reads(batch: &RecordBatch) -> crate::error::Result<Vec<NativeRead>>
+{
+	let read_ids = column(batch, "read_id")?
+		.as_fixed_size_binary_opt()
+		.ok_or_else(|| column_error("read_id"))?;
+	let signal = column(batch, "signal")?
+		.as_list_opt::<i32>()
+		.ok_or_else(|| column_error("signal"))?;
+	let end_reason_forced = column(batch, "end_reason_forced")?
+		.as_boolean_opt()
+		.ok_or_else(|| column_error("end_reason_forced"))?;
+
+	let read_number = primitive::<UInt32Type>(batch, "read_number")?;
+	let start_sample = primitive::<UInt64Type>(batch, "start")?;
+	let median_before = primitive::<Float32Type>(batch, "median_before")?;
+	let channel = primitive::<UInt16Type>(batch, "channel")?;
+	let well = primitive::<UInt8Type>(batch, "well")?;
+	let calibration_offset = primitive::<Float32Type>(batch, "calibration_offset")?;
+	let calibration_scale = primitive::<Float32Type>(batch, "calibration_scale")?;
+	let num_minknow_events = primitive::<UInt64Type>(batch, "num_minknow_events")?;
+	let tracked_scaling_scale = primitive::<Float32Type>(batch, "tracked_scaling_scale")?;
+	let tracked_scaling_shift = primitive::<Float32Type>(batch, "tracked_scaling_shift")?;
+	let predicted_scaling_scale = primitive::<Float32Type>(batch, "predicted_scaling_scale")?;
+	let predicted_scaling_shift = primitive::<Float32Type>(batch, "predicted_scaling_shift")?;
+	let num_reads_since_mux_change = primitive::<UInt32Type>(batch, "num_reads_since_mux_change")?;
+	let time_since_mux_change = primitive::<Float32Type>(batch, "time_since_mux_change")?;
+	let num_samples = primitive::<UInt64Type>(batch, "num_samples")?;
+
+	let pore_type = dictionary_strings(batch, "pore_type")?;
+	let end_reason = dictionary_strings(batch, "end_reason")?;
+	let run_info = dictionary_strings(batch, "run_info")?;
+
+	let mut reads = Vec::with_capacity(batch.num_rows());
+	for row in 0..batch.num_rows()
+	{
+		let signal_rows = signal.value(row);
+		let signal_rows = signal_rows
+			.as_primitive_opt::<UInt64Type>()
+			.ok_or_else(|| column_error("signal"))?;
+
+		reads.push(NativeRead {
+			read_id: uuid::Uuid::from_slice(read_ids.value(row))
+				.map_err(|_| column_error("read_id"))?,
+			read_number: read_number.value(row),
+			start_sample: start_sample.value(row),
+			median_before: median_before.value(row),
+			channel: channel.value(row),
+			well: well.value(row),
+			pore_type: pore_type[row].1.clone(),
+			pore_type_index: pore_type[row].0,
+			calibration_offset: calibration_offset.value(row),
+			calibration_scale: calibration_scale.value(row),
+			end_reason: end_reason[row].1.clone(),
+			end_reason_forced: end_reason_forced.value(row),
+			run_info: run_info[row].1.clone(),
+			run_info_index: run_info[row].0,
+			num_minknow_events: num_minknow_events.value(row),
+			tracked_scaling_scale: tracked_scaling_scale.value(row),
+			tracked_scaling_shift: tracked_scaling_shift.value(row),
+			predicted_scaling_scale: predicted_scaling_scale.value(row),
+			predicted_scaling_shift: predicted_scaling_shift.value(row),
+			num_reads_since_mux_change: num_reads_since_mux_change.value(row),
+			time_since_mux_change: time_since_mux_change.value(row),
+			num_samples: num_samples.value(row),
+			signal_rows: signal_rows.values().to_vec(),
+		});
+	}
+
+	Ok(reads)
+}
+
+/// Load every run info in a run info table batch.
+fn batch_run_infos(batch: &RecordBatch) -> crate::error::Result<Vec<RunInfoData>>
+{
+	let acquisition_id = string(batch, "acquisition_id")?;
+	let acquisition_start_time = primitive::<TimestampMillisecondType>(batch, "acquisition_start_time")?;
+	let adc_max = primitive::<Int16Type>(batch, "adc_max")?;
+	let adc_min = primitive::<Int16Type>(batch, "adc_min")?;
+	let experiment_name = string(batch, "experiment_name")?;
+	let flow_cell_id = string(batch, "flow_cell_id")?;
+	let flow_cell_product_code = string(batch, "flow_cell_product_code")?;
+	let protocol_name = string(batch, "protocol_name")?;
+	let protocol_run_id = string(batch, "protocol_run_id")?;
+	let protocol_start_time = primitive::<TimestampMillisecondType>(batch, "protocol_start_time")?;
+	let sample_id = string(batch, "sample_id")?;
+	let sample_rate = primitive::<UInt16Type>(batch, "sample_rate")?;
+	let sequencing_kit = string(batch, "sequencing_kit")?;
+	let sequencer_position = string(batch, "sequencer_position")?;
+	let sequencer_position_type = string(batch, "sequencer_position_type")?;
+	let software = string(batch, "software")?;
+	let system_name = string(batch, "system_name")?;
+	let system_type = string(batch, "system_type")?;
+
+	let mut run_infos = Vec::with_capacity(batch.num_rows());
+	for row in 0..batch.num_rows()
+	{
+		run_infos.push(RunInfoData {
+			acquisition_id: acquisition_id.value(row).to_string(),
+			acquisition_start_time_ms: acquisition_start_time.value(row),
+			adc_max: adc_max.value(row),
+			adc_min: adc_min.value(row),
+			context_tags: string_map(batch, "context_tags", row)?,
+			experiment_name: experiment_name.value(row).to_string(),
+			flow_cell_id: flow_cell_id.value(row).to_string(),
+			flow_cell_product_code: flow_cell_product_code.value(row).to_string(),
+			protocol_name: protocol_name.value(row).to_string(),
+			protocol_run_id: protocol_run_id.value(row).to_string(),
+			protocol_start_time_ms: protocol_start_time.value(row),
+			sample_id: sample_id.value(row).to_string(),
+			sample_rate: sample_rate.value(row),
+			sequencing_kit: sequencing_kit.value(row).to_string(),
+			sequencer_position: sequencer_position.value(row).to_string(),
+			sequencer_position_type: sequencer_position_type.value(row).to_string(),
+			software: software.value(row).to_string(),
+			system_name: system_name.value(row).to_string(),
+			system_type: system_type.value(row).to_string(),
+			tracking_id: string_map(batch, "tracking_id", row)?,
+		});
+	}
+
+	Ok(run_infos)
+}
+
+fn column_error(name: &str) -> crate::error::Error
+{
+	crate::error::Error::TypeError(format!("unexpected type for column {}", name))
+}
+
+fn column<'a>(batch: &'a RecordBatch, name: &str) -> crate::error::Result<&'a dyn Array>
+{
+	batch
+		.column_by_name(name)
+		.map(|column| column.as_ref())
+		.ok_or_else(|| crate::error::Error::KeyError(format!("table has no {} column", name)))
+}
+
+fn primitive<'a, T: ArrowPrimitiveType>(
+	batch: &'a RecordBatch,
+	name: &str,
+) -> crate::error::Result<&'a PrimitiveArray<T>>
+{
+	column(batch, name)?
+		.as_primitive_opt::<T>()
+		.ok_or_else(|| column_error(name))
+}
+
+fn string<'a>(batch: &'a RecordBatch, name: &str) -> crate::error::Result<&'a StringArray>
+{
+	column(batch, name)?
+		.as_string_opt::<i32>()
+		.ok_or_else(|| column_error(name))
+}
+
+/// Decode a single row of a map column with string keys and values.
+fn string_map(
+	batch: &RecordBatch,
+	name: &str,
+	row: usize,
+) -> crate::error::Result<std::collections::HashMap<String, String>>
+{
+	let entries = column(batch, name)?
+		.as_map_opt()
+		.ok_or_else(|| column_error(name))?
+		.value(row);
+	let keys = entries.column(0).as_string_opt::<i32>().ok_or_else(|| column_error(name))?;
+	let values = entries.column(1).as_string_opt::<i32>().ok_or_else(|| column_error(name))?;
+
+	Ok(keys
+		.iter()
+		.zip(values.iter())
+		.map(|(key, value)| {
+			(key.unwrap_or_default().to_string(), value.unwrap_or_default().to_string())
+		})
+		.collect())
+}
+
+/// Decode a dictionary encoded string column into the key and value of each row.
+fn dictionary_strings(batch: &RecordBatch, name: &str) -> crate::error::Result<Vec<(i16, String)>>
+{
+	let dictionary = column(batch, name)?
+		.as_dictionary_opt::<Int16Type>()
+		.ok_or_else(|| column_error(name))?;
+	let values = dictionary
+		.values()
+		.as_string_opt::<i32>()
+		.ok_or_else(|| column_error(name))?;
+
+	dictionary
+		.keys()
+		.iter()
+		.map(|key| {
+			let key = key.ok_or_else(|| column_error(name))?;
+			if key < 0 || key as usize >= values.len()
+			{
+				return Err(column_error(name));
+			}
+			Ok((key, values.value(key as usize).to_string()))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	/// A small V3 pod5 file with five reads split over two read batches, two run infos and
+	/// reads whose signal spans more than one signal batch.
+	fn test_file() -> PathBuf
+	{
+		Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/synthetic_v3.pod5")
+	}
+
+	#[test]
+	fn content_type_codes()
+	{
+		assert_eq!(ContentType::from_code(0), ContentType::ReadsTable);
+		assert_eq!(ContentType::from_code(1), ContentType::SignalTable);
+		assert_eq!(ContentType::from_code(2), ContentType::ReadIdIndex);
+		assert_eq!(ContentType::from_code(3), ContentType::OtherIndex);
+		assert_eq!(ContentType::from_code(4), ContentType::RunInfoTable);
+	}
+
+	#[test]
+	fn footer_tables()
+	{
+		let reader = NativeReader::from_path(test_file()).unwrap();
+
+		for content_type in
+			[ContentType::ReadsTable, ContentType::SignalTable, ContentType::RunInfoTable]
+		{
+			assert!(
+				reader.footer().contents.iter().any(|table| table.content_type == content_type),
+				"no {:?} in footer",
+				content_type
+			);
+		}
+	}
+
+	#[test]
+	fn reads_and_signal()
+	{
+		let reader = NativeReader::from_path(test_file()).unwrap();
+		let reads = reader.reads().unwrap();
+
+		assert!(!reads.is_empty());
+		assert_eq!(reads.len(), reader.count().unwrap());
+		assert_eq!(
+			reads.iter().map(|read| read.read_id).collect::<Vec<uuid::Uuid>>(),
+			reader.read_ids().unwrap()
+		);
+
+		for read in reads.iter()
+		{
+			assert_eq!(reader.signal(read).unwrap().len() as u64, read.num_samples);
+		}
+	}
+
+	#[test]
+	fn reader_api()
+	{
+		let reader = reader::Reader::from_path(test_file(), None).unwrap();
+		let read_ids = reader.read_ids().unwrap();
+
+		assert_eq!(read_ids.len(), reader.count().unwrap());
+		assert!(reader.run_info_iter().count() > 0);
+
+		let mut count = 0;
+		for read in reader.reads_iter(None)
+		{
+			let read = read.unwrap();
+			assert_eq!(read.uuid(), read_ids[count]);
+			assert_eq!(read.signal().unwrap().len() as u64, read.num_samples());
+			assert!(read.run_info().is_ok());
+			count += 1;
+		}
+		assert_eq!(count, read_ids.len());
+
+		let read = reader.get(read_ids[count - 1]).unwrap().unwrap();
+		assert_eq!(read.uuid(), read_ids[count - 1]);
+		assert!(reader.get(uuid::Uuid::nil()).unwrap().is_none());
+		assert_eq!(reader.reads_iter(vec![read_ids[0]]).count(), 1);
+	}
+
+	#[test]
+	fn fetch_and_signal_api()
+	{
+		let reader = reader::Reader::from_path(test_file(), None).unwrap();
+		let read_ids = reader.read_ids().unwrap();
+
+		let fetch = vec![read_ids[3], uuid::Uuid::nil(), read_ids[1]];
+		let fetched: Vec<uuid::Uuid> =
+			reader.reads_iter(fetch.clone()).map(|read| read.unwrap().uuid()).collect();
+		assert_eq!(fetched, vec![read_ids[1], read_ids[3]]);
+
+		let report = reader.fetch_report(&fetch).unwrap();
+		assert_eq!(report.found().len(), 2);
+		assert!(!report.is_complete());
+		assert_eq!(report.missing(), &[uuid::Uuid::nil()]);
+
+		let ordered: Vec<uuid::Uuid> = reader
+			.reads_iter_ordered(vec![read_ids[4], read_ids[0], read_ids[4]])
+			.batch_cache(1)
+			.map(|read| read.unwrap().uuid())
+			.collect();
+		assert_eq!(ordered, vec![read_ids[4], read_ids[0], read_ids[4]]);
+
+		for result in reader.reads_iter(None).with_signal()
+		{
+			let (read, signal) = result.unwrap();
+			let full = read.signal().unwrap();
+			assert_eq!(&signal[..], &full[..]);
+
+			let rows = read.signal_rows().unwrap();
+			assert_eq!(
+				rows.iter().map(|row| row.sample_count as usize).sum::<usize>(),
+				full.len()
+			);
+
+			let range = 1..full.len() - 1;
+			assert_eq!(read.signal_range(range.clone()).unwrap(), full[range].to_vec());
+			assert!(read.signal_range(0..full.len() + 1).is_err());
+		}
+	}
+}
@@ -0,0 +1,434 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::data::adc_to_pa;
+pub use crate::data::{ReadData, ReadDataBuilder, SignalRow, DEFAULT_FIELDS};
+
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Calibration and calibration extra data from the associated read.
+pub struct Calibration<'a>
+{
+	pub(crate) inner: &'a Read,
+
+	pub(crate) digitisation: Option<u16>,
+	pub(crate) range: Option<f32>,
+}
+
+impl<'a> Calibration<'a>
+{
+	/// Calibration offset.
+	pub fn offset(&self) -> f32
+	{
+		self.inner.inner.calibration_offset
+	}
+
+	/// Calibration scale.
+	pub fn scale(&self) -> f32
+	{
+		self.inner.inner.calibration_scale
+	}
+
+	/// The digitisation of the read's run info, or 0 if its run info can't be found.
+	pub fn digitisation(&mut self) -> u16
+	{
+		if let Some(digitisation) = self.digitisation
+		{
+			return digitisation;
+		}
+
+		// Matches the C API, which derives it from the run's ADC range.
+		let digitisation = self
+			.inner
+			.run_info()
+			.map(|run_info| (run_info.adc_max() as i32 - run_info.adc_min() as i32 + 1) as u16)
+			.unwrap_or(0);
+
+		self.digitisation = Some(digitisation);
+		digitisation
+	}
+
+	/// The range in picoamps covered by the digitisation.
+	pub fn range(&mut self) -> f32
+	{
+		if let Some(range) = self.range
+		{
+			return range;
+		}
+
+		let range = self.scale() * self.digitisation() as f32;
+
+		self.range = Some(range);
+		range
+	}
+}
+
+/// pod5 read information.
+/// see <https://github.com/nanoporetech/pod5-file-format/blob/master/docs/tables/reads.toml> for full pod5-file-format specification information.
+///
+/// A read keeps the file it was read from open, so reads can be collected, sent to other
+/// threads and used after iteration has moved on.
+pub struct Read
+{
+	pub(crate) inner: super::NativeRead,
+	pub(crate) file: Arc<super::NativeReader>,
+}
+
+impl Read
+{
+	/// The uncompressed signal for the associated read.
+	pub fn signal(&self) -> crate::error::Result<Vec<i16>>
+	{
+		self.file.signal(&self.inner)
+	}
+
+	/// Write the uncompressed signal for the associated read into an existing buffer.
+	/// The buffer is resized to fit the signal, reusing its allocation where possible.
+	pub fn signal_into(&self, buffer: &mut Vec<i16>) -> crate::error::Result<()>
+	{
+		buffer.clear();
+		buffer.reserve(self.num_samples() as usize);
+
+		self.file.signal_chunks(&self.inner, |samples| {
+			buffer.extend_from_slice(samples);
+			Ok(())
+		})
+	}
+
+	/// Write the uncompressed signal for the associated read into the start of a slice.
+	///
+	/// # Returns
+	///
+	/// The number of samples written, or a capacity error if the slice is too small to hold them.
+	pub fn signal_into_slice(&self, buffer: &mut [i16]) -> crate::error::Result<usize>
+	{
+		let signal_count = self.num_samples() as usize;
+		if buffer.len() < signal_count
+		{
+			return Err(crate::error::Error::CapacityError(format!(
+				"signal has {} samples, buffer only holds {}",
+				signal_count,
+				buffer.len()
+			)));
+		}
+
+		let mut written = 0;
+		self.file.signal_chunks(&self.inner, |samples| {
+			let end = written + samples.len();
+			if end > signal_count
+			{
+				return Err(crate::error::Error::CapacityError(
+					"signal rows hold more samples than the read".to_string(),
+				));
+			}
+
+			buffer[written..end].copy_from_slice(samples);
+			written = end;
+			Ok(())
+		})?;
+
+		Ok(written)
+	}
+
+	/// The signal for the associated read, calibrated to picoamps.
+	pub fn signal_pa(&self) -> crate::error::Result<Vec<f32>>
+	{
+		let mut signal = Vec::new();
+		self.signal_pa_into(&mut signal)?;
+		Ok(signal)
+	}
+
+	/// Write the signal for the associated read, calibrated to picoamps, into an existing buffer.
+	/// The buffer is cleared first, so can be reused between reads.
+	pub fn signal_pa_into(&self, buffer: &mut Vec<f32>) -> crate::error::Result<()>
+	{
+		let calibration = self.calibration();
+		let (offset, scale) = (calibration.offset(), calibration.scale());
+
+		buffer.clear();
+		buffer.reserve(self.num_samples() as usize);
+
+		self.file.signal_chunks(&self.inner, |samples| {
+			buffer.extend(samples.iter().map(|&sample| adc_to_pa(sample, offset, scale)));
+			Ok(())
+		})
+	}
+
+	/// The read id as a uuid.
+	pub fn uuid(&self) -> uuid::Uuid
+	{
+		self.inner.read_id
+	}
+
+	/// The read number.
+	pub fn read_number(&self) -> u32
+	{
+		self.inner.read_number
+	}
+
+	/// Number of samples in the channel before read was taken.
+	pub fn start_sample(&self) -> u64
+	{
+		self.inner.start_sample
+	}
+
+	/// Current level before read taken.
+	pub fn median_before(&self) -> f32
+	{
+		self.inner.median_before
+	}
+
+	/// 1-indexed channel.
+	pub fn channel(&self) -> u16
+	{
+		self.inner.channel
+	}
+
+	/// 1-indexed well number.
+	pub fn well(&self) -> u8
+	{
+		self.inner.well
+	}
+
+	/// Raw pore type.
+	pub fn pore_type(&self) -> i16
+	{
+		self.inner.pore_type_index
+	}
+
+	/// Attempts to get the pore type as a String.
+	pub fn pore_type_string(&self) -> crate::error::Result<String>
+	{
+		Ok(self.inner.pore_type.clone())
+	}
+
+	/// Calibration and extra calibration data for the read.
+	pub fn calibration(&self) -> Calibration<'_>
+	{
+		Calibration {
+			inner: self,
+			digitisation: None,
+			range: None,
+		}
+	}
+
+	/// See [crate::endreason::EndReason]
+	pub fn end_reason(&self) -> crate::endreason::EndReason
+	{
+		crate::endreason::EndReason::end_reason_from_name(&self.inner.end_reason)
+	}
+
+	/// True if the read was forcefully ended.
+	pub fn end_reason_forced(&self) -> bool
+	{
+		self.inner.end_reason_forced
+	}
+
+	/// Which run info number.
+	pub fn run_info_num(&self) -> i16
+	{
+		self.inner.run_info_index
+	}
+
+	/// The runinfo struct from the meta data for the associated read.
+	pub fn run_info(&self) -> crate::error::Result<super::runinfo::RunInfo>
+	{
+		let run_info = self
+			.file
+			.run_infos()?
+			.iter()
+			.find(|run_info| run_info.acquisition_id == self.inner.run_info)
+			.ok_or_else(|| {
+				crate::error::Error::KeyError(format!("run info {} not found", self.inner.run_info))
+			})?;
+
+		Ok(super::runinfo::RunInfo {
+			inner: run_info.clone(),
+		})
+	}
+
+	/// Number of minknow events that the read contains.
+	pub fn num_minknow_events(&self) -> u64
+	{
+		self.inner.num_minknow_events
+	}
+
+	/// Tracked scaling scale.
+	pub fn tracked_scaling_scale(&self) -> f32
+	{
+		self.inner.tracked_scaling_scale
+	}
+
+	/// Tracked scaling shift.
+	pub fn tracked_scaling_shift(&self) -> f32
+	{
+		self.inner.tracked_scaling_shift
+	}
+
+	/// Predicted scaling scale.
+	pub fn predicted_scaling_scale(&self) -> f32
+	{
+		self.inner.predicted_scaling_scale
+	}
+
+	/// Predicted scaling shift.
+	pub fn predicted_scaling_shift(&self) -> f32
+	{
+		self.inner.predicted_scaling_shift
+	}
+
+	/// Number of reads since last mux change.
+	pub fn num_reads_since_mux_change(&self) -> u32
+	{
+		self.inner.num_reads_since_mux_change
+	}
+
+	/// Time in seconds since last mux change.
+	pub fn time_since_mux_change(&self) -> f32
+	{
+		self.inner.time_since_mux_change
+	}
+
+	/// Number of signal row count.
+	pub fn signal_row_count(&self) -> i64
+	{
+		self.inner.signal_rows.len() as i64
+	}
+
+	/// Number of signal samples.
+	pub fn num_samples(&self) -> u64
+	{
+		self.inner.num_samples
+	}
+
+	/// Describes each stored chunk of the read's signal, in sample order.
+	pub fn signal_rows(&self) -> crate::error::Result<Vec<SignalRow>>
+	{
+		self.file.signal_rows(&self.inner)
+	}
+
+	/// The uncompressed signal for a range of samples within the read.
+	///
+	/// Only the stored signal chunks overlapping the range are decompressed, so this is much
+	/// cheaper than [`Read::signal`] when only part of a long read is needed.
+	pub fn signal_range(&self, range: std::ops::Range<usize>) -> crate::error::Result<Vec<i16>>
+	{
+		let num_samples = self.num_samples() as usize;
+		if range.start > range.end || range.end > num_samples
+		{
+			return Err(crate::error::Error::IndexError(format!(
+				"signal range {}..{} out of bounds for read with {} samples",
+				range.start, range.end, num_samples
+			)));
+		}
+
+		self.file.signal_range(&self.inner, range)
+	}
+
+	/// Copy the read, including its signal, into an owned [`ReadData`].
+	///
+	/// `pore_type` and `run_info` keep the indices used by the file being read.
+	pub fn to_owned(&self) -> crate::error::Result<ReadData>
+	{
+		let calibration = self.calibration();
+
+		Ok(ReadData {
+			read_id: self.uuid(),
+			read_number: self.read_number(),
+			start_sample: self.start_sample(),
+			median_before: self.median_before(),
+			channel: self.channel(),
+			well: self.well(),
+			pore_type: self.pore_type(),
+			calibration_offset: calibration.offset(),
+			calibration_scale: calibration.scale(),
+			end_reason: self.end_reason(),
+			end_reason_forced: self.end_reason_forced(),
+			run_info: self.run_info_num(),
+			num_minknow_events: self.num_minknow_events(),
+			tracked_scaling_scale: self.tracked_scaling_scale(),
+			tracked_scaling_shift: self.tracked_scaling_shift(),
+			predicted_scaling_scale: self.predicted_scaling_scale(),
+			predicted_scaling_shift: self.predicted_scaling_shift(),
+			num_reads_since_mux_change: self.num_reads_since_mux_change(),
+			time_since_mux_change: self.time_since_mux_change(),
+			signal: self.signal()?,
+		})
+	}
+}
+
+impl fmt::Display for Read
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+	{
+		let calibration = self.calibration();
+
+		writeln!(f, "uuid = {}", &self.uuid())?;
+		writeln!(f, "signal = {:?}", self.signal().unwrap_or(vec![0i16; 0]))?;
+		writeln!(f, "read_number = {}", self.read_number())?;
+		writeln!(f, "start_sample = {}", self.start_sample())?;
+		writeln!(f, "median_before = {}", self.median_before())?;
+		writeln!(f, "channel = {}", self.channel())?;
+		writeln!(f, "well = {}", self.well())?;
+		writeln!(f, "pore_type = {}", self.pore_type())?;
+		writeln!(f, "calibration_offset = {}", calibration.offset())?;
+		writeln!(f, "calibration_scale = {}", calibration.scale())?;
+		writeln!(f, "end_reason = {}", self.end_reason())?;
+		writeln!(f, "end_reason_forced = {}", self.end_reason_forced())?;
+		writeln!(f, "run_info = {}", self.run_info_num())?;
+		writeln!(f, "num_minknow_events = {}", self.num_minknow_events())?;
+		writeln!(f, "tracked_scaling_scale = {}", self.tracked_scaling_scale())?;
+		writeln!(f, "tracked_scaling_shift = {}", self.tracked_scaling_shift())?;
+		writeln!(f, "predicted_scaling_scale = {}", self.predicted_scaling_scale())?;
+		writeln!(f, "predicted_scaling_shift = {}", self.predicted_scaling_shift())?;
+		writeln!(f, "num_reads_since_mux_change = {}", self.num_reads_since_mux_change())?;
+		writeln!(f, "time_since_mux_change = {}", self.time_since_mux_change())?;
+		writeln!(f, "signal_row_count = {}", self.signal_row_count())?;
+		writeln!(f, "num_samples = {}", self.num_samples())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Read
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		// Start serializing the struct with the specified number of fields
+		let mut state = serializer.serialize_struct("Read", 21)?;
+
+		let calibration = self.calibration();
+
+		// Serialize each field with its name
+		state.serialize_field("uuid", &self.uuid())?;
+		state.serialize_field("signal", &self.signal().unwrap_or(vec![0i16; 0]))?;
+		state.serialize_field("read_number", &self.read_number())?;
+		state.serialize_field("start_sample", &self.start_sample())?;
+		state.serialize_field("median_before", &self.median_before())?;
+		state.serialize_field("channel", &self.channel())?;
+		state.serialize_field("well", &self.well())?;
+		state.serialize_field("pore_type", &self.pore_type())?;
+		state.serialize_field("calibration_offset", &calibration.offset())?;
+		state.serialize_field("calibration_scale", &calibration.scale())?;
+		state.serialize_field("end_reason", &self.end_reason())?;
+		state.serialize_field("end_reason_forced", &self.end_reason_forced())?;
+		state.serialize_field("run_info", &self.run_info_num())?;
+		state.serialize_field("num_minknow_events", &self.num_minknow_events())?;
+		state.serialize_field("tracked_scaling_scale", &self.tracked_scaling_scale())?;
+		state.serialize_field("tracked_scaling_shift", &self.tracked_scaling_shift())?;
+		state.serialize_field("predicted_scaling_scale", &self.predicted_scaling_scale())?;
+		state.serialize_field("predicted_scaling_shift", &self.predicted_scaling_shift())?;
+		state.serialize_field(
+			"num_reads_since_mux_change",
+			&self.num_reads_since_mux_change(),
+		)?;
+		state.serialize_field("time_since_mux_change", &self.time_since_mux_change())?;
+		state.serialize_field("signal_row_count", &self.signal_row_count())?;
+		state.serialize_field("num_samples", &self.num_samples())?;
+
+		// End serialization
+		state.end()
+	}
+}
@@ -0,0 +1,467 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+pub use super::read::*;
+pub use super::reads::*;
+pub use crate::data::FetchReport;
+use super::NativeReader;
+
+#[cfg(feature = "recursive")]
+use ignore::{WalkBuilder, types::TypesBuilder};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Contains reader options, passed to Reader.
+pub struct ReaderOptions
+{
+	index_files: bool,
+}
+
+impl ReaderOptions
+{
+	/// Create a reader options struct to specify when opening pod5 files.
+	/// The native backend never maps files, so `force_disable_file_mapping` has no effect and is
+	/// kept for compatibility.
+	pub fn new(_force_disable_file_mapping: bool) -> ReaderOptions
+	{
+		ReaderOptions { index_files: false }
+	}
+
+	/// Store the read id index used by [`Reader::get`] in a sidecar file next to each pod5 file,
+	/// e.g. `reads.pod5.idx`, and reuse it when the file is opened again.
+	///
+	/// Sidecars are shared with the C++ backend, so either can reuse an index the other wrote.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let options = ReaderOptions::new(false).index_files(true);
+	/// let reader = Reader::from_path("/folder/containing/pod5files", options)?;
+	/// ```
+	pub fn index_files(mut self, index_files: bool) -> ReaderOptions
+	{
+		self.index_files = index_files;
+		self
+	}
+}
+
+/// The number of decoded read batches [`Reader::get`] keeps.
+const GET_BATCH_CACHE: usize = 4;
+
+/// Reader for pod5 files that doesn't use the pod5 C++ library.
+/// ```
+/// use decapod::reader::Reader;
+/// use uuid::Uuid;
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>>
+/// {
+///     let reader = Reader::from_path("example.pod5", None)?;
+///
+///     println!("{:?}", &reader.read_ids()?);
+///     Ok(())
+/// }
+/// ````
+pub struct Reader
+{
+	pub(crate) inner: Vec<Arc<NativeReader>>,
+	index: OnceLock<crate::index::ReadIndex>,
+	index_files: bool,
+	batches: Mutex<BatchCache>,
+}
+
+impl Reader
+{
+	fn new(readers: Vec<Arc<NativeReader>>, options: Option<ReaderOptions>) -> Reader
+	{
+		Reader {
+			inner: readers,
+			index: OnceLock::new(),
+			index_files: options.is_some_and(|options| options.index_files),
+			batches: Mutex::new(BatchCache::new(GET_BATCH_CACHE)),
+		}
+	}
+
+	/// Opens a pod5 file or directory of pod5 files for reading.
+	/// # Arguments
+	///
+	/// * `path` - The path to either a pod5 or a directory containing pod5 files.
+	/// * `options` - the [`ReaderOptions`] object. Pass None to use no options (typical).
+	pub fn from_path<P: AsRef<Path>, R: Into<Option<ReaderOptions>>>(
+		path: P,
+		options: R,
+	) -> crate::error::Result<Reader>
+	{
+		Self::from_iter(std::iter::once(path), options)
+	}
+
+	/// Opens a combinations of both pod5 file paths and directories containing pod5 files.
+	/// # Arguments
+	///
+	/// * `paths` - The vector of pod5 file and directory paths.
+	/// * `options` - the [`ReaderOptions`] object. Pass None to use no options (typical).
+	pub fn from_vec<P, R: Into<Option<ReaderOptions>>>(
+		paths: Vec<P>,
+		options: R,
+	) -> crate::error::Result<Reader>
+	where
+		P: AsRef<Path>,
+	{
+		Self::from_iter(paths.iter(), options)
+	}
+
+	/// Opens a combinations of both pod5 file paths and directories containing pod5 files.
+	/// # Arguments
+	///
+	/// * `iter` - The iterator object consisting of pod5 file and directory paths.
+	/// * `options` - the [`ReaderOptions`] object. Pass None to use no options (typical).
+	pub fn from_iter<P, I, R: Into<Option<ReaderOptions>>>(
+		iter: I,
+		options: R,
+	) -> crate::error::Result<Reader>
+	where
+		I: IntoIterator<Item = P>,
+		P: AsRef<Path>,
+	{
+		let mut readers = Vec::new();
+		for path in iter
+		{
+			if path.as_ref().is_file()
+			{
+				readers.push(Arc::new(NativeReader::from_path(path)?));
+			}
+			else
+			{
+				#[cfg(feature = "recursive")]
+				readers.extend(Self::_readers_from_dir(path)?);
+			}
+		}
+
+		Ok(Reader::new(readers, options.into()))
+	}
+
+	#[cfg(feature = "recursive")]
+	fn _readers_from_dir<P: AsRef<Path>>(path: P) -> crate::error::Result<Vec<Arc<NativeReader>>>
+	{
+		let mut types_builder = TypesBuilder::new();
+		types_builder.add("pod5", "*.pod5").expect("REASON");
+		types_builder.select("pod5");
+		let matcher = types_builder.build().unwrap();
+
+		let walker = WalkBuilder::new(path).types(matcher).build();
+
+		let mut results = Vec::new();
+
+		for entry in walker.flatten()
+		{
+			let path = entry.path();
+			if path.is_file()
+			{
+				results.push(Arc::new(NativeReader::from_path(path)?));
+			}
+		}
+
+		Ok(results)
+	}
+
+	/// Returns the total number of read ids from all open pod5 files.
+	pub fn count(&self) -> crate::error::Result<usize>
+	{
+		self.inner
+			.iter()
+			.try_fold(0usize, |acc, item| item.count().map(|count| acc + count))
+	}
+
+	/// Returns the uuids for every read id from all open pod5 files.
+	pub fn read_ids(&self) -> crate::error::Result<Vec<uuid::Uuid>>
+	{
+		self.inner.iter().try_fold(Vec::new(), |mut acc, item| {
+			acc.extend(item.read_ids()?);
+			Ok(acc)
+		})
+	}
+
+	/// Returns a vector of all the file info structs found within the open pod5 files.
+	pub fn info(&self) -> Vec<crate::error::Result<super::fileinfo::FileInfo>>
+	{
+		self.inner
+			.iter()
+			.map(|reader| Ok(super::fileinfo::FileInfo::new(reader.footer())))
+			.collect()
+	}
+
+	/// Obtain the runinfo iterator.
+	pub fn run_info_iter(&self) -> super::runinfo::RunInfoIter<'_>
+	{
+		super::runinfo::RunInfoIter {
+			reader: self.inner.iter(),
+			current_row: 0,
+			current_reader: None,
+		}
+	}
+
+	/// Create a Reads iterator for reads found within the open pod5 files.
+	///
+	/// # Arguments
+	///
+	/// * `fetch` - Specify None to obtain all reads, or a vector of uuids for specific reads of interest
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// let read_ids = vec![uuid!("002fde30-9e23-4125-9eae-d112c18a81a7")];
+	/// for read in reader.reads_iter(read_ids)
+	/// {
+	///     let read = read?;
+	///     println!("{}", read.uuid());
+	/// }
+	/// ```
+	///
+	/// # Returns
+	///
+	/// A reads iterator.
+	pub fn reads_iter<U: Into<Option<Vec<uuid::Uuid>>>>(&self, fetch: U) -> Reads<'_>
+	{
+		Reads::new(self, fetch.into())
+	}
+
+	/// Obtain a single read by its read id.
+	///
+	/// An index of every read id in the open files is built the first time this is called, after
+	/// which each lookup only decodes the read's batch. The most recently used batches are kept
+	/// decoded, so stepping between neighbouring reads does not decode their batch again.
+	///
+	/// See [`ReaderOptions::index_files`] to keep the index on disk between runs.
+	///
+	/// # Arguments
+	///
+	/// * `read_id` - The read id to look up.
+	///
+	/// # Returns
+	///
+	/// The read, or None if no open file contains the read id.
+	pub fn get(&self, read_id: uuid::Uuid) -> crate::error::Result<Option<Read>>
+	{
+		let Some(location) = self.index()?.get(&read_id)
+		else
+		{
+			return Ok(None);
+		};
+
+		let reads = self
+			.batches
+			.lock()
+			.map_err(|e| crate::error::Error::UnknownError(e.to_string()))?
+			.get(&self.inner, location.file, location.batch)?;
+
+		Ok(Some(Read {
+			inner: reads[location.row].clone(),
+			file: self.inner[location.file].clone(),
+		}))
+	}
+
+	/// Report which of the given read ids are found in the open files, and which are missing.
+	///
+	/// [`Reader::reads_iter`] skips read ids that are not found, so this can be used to check a
+	/// fetch up front. Uses the same index as [`Reader::get`].
+	///
+	/// # Arguments
+	///
+	/// * `fetch` - The read ids to look for.
+	///
+	/// # Returns
+	///
+	/// A [`FetchReport`], listing ids in the order they were requested.
+	pub fn fetch_report(&self, fetch: &[uuid::Uuid]) -> crate::error::Result<FetchReport>
+	{
+		let index = self.index()?;
+		let mut report = FetchReport::default();
+
+		for read_id in fetch
+		{
+			match index.get(read_id)
+			{
+				Some(location) => report
+					.found
+					.push((*read_id, self.inner[location.file].path().to_path_buf())),
+				None => report.missing.push(*read_id),
+			}
+		}
+
+		Ok(report)
+	}
+
+	/// Create an iterator over specific reads, yielding them in the order they were requested.
+	///
+	/// Each read is looked up through the index used by [`Reader::get`], and a small number of
+	/// decoded read batches are kept, see [`OrderedReads::batch_cache`]. A read id that is not
+	/// found yields a `KeyError` in its place, so results stay aligned with the requested ids.
+	///
+	/// # Arguments
+	///
+	/// * `fetch` - The read ids to obtain, in the order they should be returned.
+	///
+	/// # Returns
+	///
+	/// An ordered reads iterator.
+	pub fn reads_iter_ordered(&self, fetch: Vec<uuid::Uuid>) -> OrderedReads<'_>
+	{
+		OrderedReads::new(self, fetch)
+	}
+
+	/// Create a parallel iterator over reads found within the open pod5 files.
+	/// Requires the rayon feature to be enabled.
+	///
+	/// Read batches from every open file are distributed over the rayon thread pool, and each
+	/// batch is decoded, along with the signal of its reads, on the thread that visits it. Reads
+	/// are not returned in file order.
+	///
+	/// # Arguments
+	///
+	/// * `fetch` - Specify None to obtain all reads, or a vector of uuids for specific reads of interest
+	///
+	/// # Returns
+	///
+	/// A rayon parallel iterator of reads.
+	#[cfg(feature = "rayon")]
+	pub fn par_reads<U: Into<Option<Vec<uuid::Uuid>>>>(
+		&self,
+		fetch: U,
+	) -> impl ParallelIterator<Item = crate::error::Result<Read>> + '_
+	{
+		let mut batches = Vec::new();
+		let mut errors = Vec::new();
+
+		match fetch.into()
+		{
+			Some(fetch) => match self.fetch_path(&fetch)
+			{
+				Ok(fetch_path) => batches.extend(
+					fetch_path
+						.into_iter()
+						.map(|(file, batch, rows)| (file, batch, Some(rows))),
+				),
+				Err(e) => errors.push(e),
+			},
+			None =>
+			{
+				for (file, reader) in self.inner.iter().enumerate()
+				{
+					match reader.reads_batch_row_counts()
+					{
+						Ok(batch_row_counts) => batches
+							.extend((0..batch_row_counts.len()).map(|batch| (file, batch, None))),
+						Err(e) => errors.push(e),
+					}
+				}
+			}
+		}
+
+		errors.into_par_iter().map(Err).chain(batches.into_par_iter().flat_map_iter(
+			move |(file, batch, rows)| BatchReads::new(&self.inner[file], batch, rows),
+		))
+	}
+
+	pub(crate) fn index(&self) -> crate::error::Result<&crate::index::ReadIndex>
+	{
+		if let Some(index) = self.index.get()
+		{
+			return Ok(index);
+		}
+
+		let index = crate::index::ReadIndex::build(&self.inner, self.index_files)?;
+		Ok(self.index.get_or_init(|| index))
+	}
+
+	/// The file, batch and rows of the requested reads, in file order. Read ids that are not
+	/// found are left out.
+	pub(crate) fn fetch_path(
+		&self,
+		fetch: &[uuid::Uuid],
+	) -> crate::error::Result<Vec<(usize, usize, Vec<u32>)>>
+	{
+		let index = self.index()?;
+
+		let mut fetch_path: BTreeMap<(usize, usize), Vec<u32>> = BTreeMap::new();
+		for location in fetch.iter().filter_map(|read_id| index.get(read_id))
+		{
+			fetch_path
+				.entry((location.file, location.batch))
+				.or_default()
+				.push(location.row as u32);
+		}
+
+		Ok(fetch_path
+			.into_iter()
+			.map(|((file, batch), mut rows)| {
+				rows.sort_unstable();
+				rows.dedup();
+				(file, batch, rows)
+			})
+			.collect())
+	}
+}
+
+impl TryFrom<PathBuf> for Reader
+{
+	type Error = crate::error::Error;
+
+	fn try_from(path: PathBuf) -> crate::error::Result<Reader>
+	{
+		Reader::from_path(path, None)
+	}
+}
+
+impl TryFrom<&str> for Reader
+{
+	type Error = crate::error::Error;
+
+	fn try_from(path: &str) -> crate::error::Result<Reader>
+	{
+		Reader::from_path(PathBuf::from(path), None)
+	}
+}
+
+impl TryFrom<String> for Reader
+{
+	type Error = crate::error::Error;
+
+	fn try_from(path: String) -> crate::error::Result<Reader>
+	{
+		Reader::from_path(PathBuf::from(path), None)
+	}
+}
+
+impl TryFrom<Vec<PathBuf>> for Reader
+{
+	type Error = crate::error::Error;
+
+	fn try_from(paths: Vec<PathBuf>) -> crate::error::Result<Reader>
+	{
+		Reader::from_vec(paths, None)
+	}
+}
+
+impl TryFrom<Vec<&str>> for Reader
+{
+	type Error = crate::error::Error;
+
+	fn try_from(paths: Vec<&str>) -> crate::error::Result<Reader>
+	{
+		Reader::from_iter(paths.into_iter().map(PathBuf::from), None)
+	}
+}
+
+impl TryFrom<Vec<String>> for Reader
+{
+	type Error = crate::error::Error;
+
+	fn try_from(paths: Vec<String>) -> crate::error::Result<Reader>
+	{
+		Reader::from_iter(paths.into_iter().map(PathBuf::from), None)
+	}
+}
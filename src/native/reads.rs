@@ -0,0 +1,417 @@
+use std::collections::VecDeque;
+use std::io::BufReader;
+use std::sync::{Arc, Mutex};
+
+use arrow::ipc::reader::FileReader;
+
+pub use crate::data::SignalBuffer;
+use super::read::Read;
+use super::{NativeRead, NativeReader};
+use crate::signaltable::EmbeddedFile;
+
+/// The reads of a decoded read batch.
+type ReadBatch = Arc<Vec<NativeRead>>;
+
+/// The most recently used decoded read batches, keyed by file and batch index.
+pub(crate) struct BatchCache
+{
+	batches: VecDeque<((usize, usize), ReadBatch)>,
+	capacity: usize,
+}
+
+impl BatchCache
+{
+	pub(crate) fn new(capacity: usize) -> BatchCache
+	{
+		BatchCache {
+			batches: VecDeque::new(),
+			capacity: capacity.max(1),
+		}
+	}
+
+	/// Keep at most `capacity` batches decoded, and always at least one.
+	pub(crate) fn set_capacity(&mut self, capacity: usize)
+	{
+		self.capacity = capacity.max(1);
+		while self.batches.len() > self.capacity
+		{
+			self.batches.pop_front();
+		}
+	}
+
+	/// Find a decoded batch, or decode it, marking it as the most recently used.
+	pub(crate) fn get(
+		&mut self,
+		files: &[Arc<NativeReader>],
+		file: usize,
+		batch: usize,
+	) -> crate::error::Result<ReadBatch>
+	{
+		let key = (file, batch);
+
+		match self.batches.iter().position(|(loaded, _)| *loaded == key)
+		{
+			Some(position) =>
+			{
+				let entry = self.batches.remove(position).unwrap();
+				self.batches.push_back(entry);
+			}
+			None =>
+			{
+				let reads = Arc::new(files[file].reads_batch(batch)?);
+				if self.batches.len() == self.capacity
+				{
+					self.batches.pop_front();
+				}
+
+				self.batches.push_back((key, reads));
+			}
+		}
+
+		Ok(self.batches.back().unwrap().1.clone())
+	}
+}
+
+/// Decode a reads table batch, keeping either every read or only the given rows.
+fn batch_rows(
+	file: &NativeReader,
+	batch: usize,
+	rows: Option<Vec<u32>>,
+) -> crate::error::Result<Vec<NativeRead>>
+{
+	let mut reads = file.reads_batch(batch)?;
+
+	let Some(rows) = rows
+	else
+	{
+		return Ok(reads);
+	};
+
+	rows.into_iter()
+		.map(|row| {
+			reads
+				.get_mut(row as usize)
+				.map(std::mem::take)
+				.ok_or_else(|| {
+					crate::error::Error::IndexError(format!(
+						"row {} out of range for read batch {}",
+						row, batch
+					))
+				})
+		})
+		.collect()
+}
+
+/// Iterator for obtaining each read.
+/// See [`super::reader::Reader::reads_iter`] for documentation.
+pub struct Reads<'a>
+{
+	reader: &'a super::reader::Reader,
+	files: std::slice::Iter<'a, Arc<NativeReader>>,
+	batches: Option<(Arc<NativeReader>, FileReader<BufReader<EmbeddedFile>>)>,
+
+	file: Option<Arc<NativeReader>>,
+	reads: std::vec::IntoIter<NativeRead>,
+
+	fetch: Option<Vec<uuid::Uuid>>,
+	fetch_path: Option<std::vec::IntoIter<(usize, usize, Vec<u32>)>>,
+}
+
+impl<'a> Reads<'a>
+{
+	pub(crate) fn new(reader: &'a super::reader::Reader, fetch: Option<Vec<uuid::Uuid>>) -> Reads<'a>
+	{
+		Reads {
+			reader,
+			files: reader.inner.iter(),
+			batches: None,
+			file: None,
+			reads: Vec::new().into_iter(),
+			fetch,
+			fetch_path: None,
+		}
+	}
+
+	/// Load the signal for every read as it is visited, reusing signal buffers once the
+	/// previous ones have been dropped.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// for result in reader.reads_iter(None).with_signal()
+	/// {
+	///     let (read, signal) = result?;
+	///     println!("{} has {} samples", read.uuid(), signal.len());
+	/// }
+	/// ```
+	pub fn with_signal(self) -> SignalReads<'a>
+	{
+		SignalReads {
+			reads: self,
+			pool: Arc::new(Mutex::new(Vec::new())),
+		}
+	}
+
+	/// Decode the next batch of every open file in turn.
+	fn next_batch(&mut self) -> Option<crate::error::Result<()>>
+	{
+		loop
+		{
+			let Some((file, batches)) = &mut self.batches
+			else
+			{
+				let file = self.files.next()?;
+				match file.reads_table_reader()
+				{
+					Ok(batches) => self.batches = Some((file.clone(), batches)),
+					Err(e) => return Some(Err(e)),
+				}
+				continue;
+			};
+
+			let file = file.clone();
+			let reads = match batches.next()
+			{
+				Some(batch) => batch
+					.map_err(crate::error::Error::from)
+					.and_then(|batch| super::batch_reads(&batch)),
+				None =>
+				{
+					self.batches = None;
+					continue;
+				}
+			};
+
+			return Some(match reads
+			{
+				Ok(reads) =>
+				{
+					self.file = Some(file);
+					self.reads = reads.into_iter();
+					Ok(())
+				}
+				Err(e) =>
+				{
+					// Skip the rest of a file once one of its batches can't be decoded.
+					self.batches = None;
+					Err(e)
+				}
+			});
+		}
+	}
+
+	/// Decode the next batch holding any of the requested reads, keeping only those reads.
+	fn next_fetch_batch(&mut self) -> Option<crate::error::Result<()>>
+	{
+		if self.fetch_path.is_none()
+		{
+			let fetch_path = match self.reader.fetch_path(self.fetch.as_deref().unwrap_or_default())
+			{
+				Ok(fetch_path) => fetch_path,
+				Err(e) =>
+				{
+					self.fetch_path = Some(Vec::new().into_iter());
+					return Some(Err(e));
+				}
+			};
+			self.fetch_path = Some(fetch_path.into_iter());
+		}
+
+		let (file, batch, rows) = self.fetch_path.as_mut().unwrap().next()?;
+		let reader = self.reader;
+		let file = &reader.inner[file];
+
+		Some(batch_rows(file, batch, Some(rows)).map(|reads| {
+			self.file = Some(file.clone());
+			self.reads = reads.into_iter();
+		}))
+	}
+}
+
+impl<'a> Iterator for Reads<'a>
+{
+	type Item = crate::error::Result<Read>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		loop
+		{
+			if let Some(file) = &self.file
+			{
+				if let Some(inner) = self.reads.next()
+				{
+					return Some(Ok(Read {
+						inner,
+						file: file.clone(),
+					}));
+				}
+			}
+
+			let loaded = match self.fetch
+			{
+				Some(_) => self.next_fetch_batch()?,
+				None => self.next_batch()?,
+			};
+
+			if let Err(e) = loaded
+			{
+				return Some(Err(e));
+			}
+		}
+	}
+}
+
+/// Iterator for obtaining each read along with its signal.
+/// See [`Reads::with_signal`] for documentation.
+pub struct SignalReads<'a>
+{
+	reads: Reads<'a>,
+	pool: Arc<Mutex<Vec<Vec<i16>>>>,
+}
+
+impl<'a> Iterator for SignalReads<'a>
+{
+	type Item = crate::error::Result<(Read, SignalBuffer)>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		let read = match self.reads.next()?
+		{
+			Ok(read) => read,
+			Err(e) => return Some(Err(e)),
+		};
+
+		let mut signal = SignalBuffer::from_pool(&self.pool);
+
+		match read.signal_into(&mut signal)
+		{
+			Ok(()) => Some(Ok((read, signal))),
+			Err(e) => Some(Err(e)),
+		}
+	}
+}
+
+/// Number of read batches kept decoded by [`OrderedReads`] unless set with
+/// [`OrderedReads::batch_cache`].
+const ORDERED_BATCH_CACHE: usize = 8;
+
+/// Iterator for obtaining reads in the order they were requested.
+/// See [`super::reader::Reader::reads_iter_ordered`] for documentation.
+pub struct OrderedReads<'a>
+{
+	reader: &'a super::reader::Reader,
+	fetch: std::vec::IntoIter<uuid::Uuid>,
+	batches: BatchCache,
+}
+
+impl<'a> OrderedReads<'a>
+{
+	pub(crate) fn new(reader: &'a super::reader::Reader, fetch: Vec<uuid::Uuid>) -> OrderedReads<'a>
+	{
+		OrderedReads {
+			reader,
+			fetch: fetch.into_iter(),
+			batches: BatchCache::new(ORDERED_BATCH_CACHE),
+		}
+	}
+
+	/// Set how many read batches are kept decoded, dropping the least recently used batch once
+	/// the limit is reached. Defaults to 8, and at least one batch is always kept.
+	pub fn batch_cache(mut self, batches: usize) -> OrderedReads<'a>
+	{
+		self.batches.set_capacity(batches);
+		self
+	}
+}
+
+impl<'a> Iterator for OrderedReads<'a>
+{
+	type Item = crate::error::Result<Read>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		let read_id = self.fetch.next()?;
+
+		let location = match self.reader.index()
+		{
+			Ok(index) => index.get(&read_id),
+			Err(e) => return Some(Err(e)),
+		};
+
+		let Some(location) = location
+		else
+		{
+			return Some(Err(crate::error::Error::KeyError(format!(
+				"read id not found: {}",
+				read_id
+			))));
+		};
+
+		let reads = match self.batches.get(&self.reader.inner, location.file, location.batch)
+		{
+			Ok(reads) => reads,
+			Err(e) => return Some(Err(e)),
+		};
+
+		Some(Ok(Read {
+			inner: reads[location.row].clone(),
+			file: self.reader.inner[location.file].clone(),
+		}))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>)
+	{
+		self.fetch.size_hint()
+	}
+}
+
+/// Iterator over the reads of a single read batch.
+/// See [`super::reader::Reader::par_reads`] for documentation.
+#[cfg(feature = "rayon")]
+pub struct BatchReads
+{
+	file: Arc<NativeReader>,
+	error: Option<crate::error::Error>,
+	reads: std::vec::IntoIter<NativeRead>,
+}
+
+#[cfg(feature = "rayon")]
+impl BatchReads
+{
+	pub(crate) fn new(file: &Arc<NativeReader>, batch: usize, rows: Option<Vec<u32>>) -> BatchReads
+	{
+		let mut batch_reads = BatchReads {
+			file: file.clone(),
+			error: None,
+			reads: Vec::new().into_iter(),
+		};
+
+		match batch_rows(file, batch, rows)
+		{
+			Ok(reads) => batch_reads.reads = reads.into_iter(),
+			Err(e) => batch_reads.error = Some(e),
+		}
+
+		batch_reads
+	}
+}
+
+#[cfg(feature = "rayon")]
+impl Iterator for BatchReads
+{
+	type Item = crate::error::Result<Read>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		if let Some(e) = self.error.take()
+		{
+			return Some(Err(e));
+		}
+
+		Some(Ok(Read {
+			inner: self.reads.next()?,
+			file: self.file.clone(),
+		}))
+	}
+}
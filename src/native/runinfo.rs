@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+pub use crate::data::RunInfoData;
+
+/// Run information metadata from the pod5 such as experiment name or flowcell ID.
+/// Obtained either by the [`super::reader::Reader::run_info_iter`] function for all run info objects,
+/// or by calling [`super::reader::Read::run_info`] directly on a read.
+pub struct RunInfo
+{
+	pub(crate) inner: RunInfoData,
+}
+
+impl RunInfo
+{
+	/// Acquisition id.
+	pub fn acquisition_id(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.acquisition_id)
+	}
+
+	/// Acquisition start time in miliseconds.
+	pub fn acquisition_start_time_ms(&self) -> i64
+	{
+		self.inner.acquisition_start_time_ms
+	}
+
+	/// Max adc.
+	pub fn adc_max(&self) -> i16
+	{
+		self.inner.adc_max
+	}
+
+	/// Min adc.
+	pub fn adc_min(&self) -> i16
+	{
+		self.inner.adc_min
+	}
+
+	/// Context data.
+	pub fn context_tags(&self) -> HashMap<String, String>
+	{
+		self.inner.context_tags.clone()
+	}
+
+	/// Experiment name.
+	pub fn experiment_name(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.experiment_name)
+	}
+
+	/// Flowcell ID.
+	pub fn flow_cell_id(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.flow_cell_id)
+	}
+
+	/// Flowcell product code.
+	pub fn flow_cell_product_code(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.flow_cell_product_code)
+	}
+
+	/// Protocol name.
+	pub fn protocol_name(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.protocol_name)
+	}
+
+	/// Protocol run id.
+	pub fn protocol_run_id(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.protocol_run_id)
+	}
+
+	/// Start time.
+	pub fn protocol_start_time_ms(&self) -> i64
+	{
+		self.inner.protocol_start_time_ms
+	}
+
+	/// Sample id.
+	pub fn sample_id(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.sample_id)
+	}
+
+	/// The sample rate of the flowcell.
+	pub fn sample_rate(&self) -> u16
+	{
+		self.inner.sample_rate
+	}
+
+	/// The sequencing kit used.
+	pub fn sequencing_kit(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.sequencing_kit)
+	}
+
+	/// Sequencer position.
+	pub fn sequencer_position(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.sequencer_position)
+	}
+
+	/// Sequencer position type.
+	pub fn sequencer_position_type(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.sequencer_position_type)
+	}
+
+	/// Software used for sequencing.
+	pub fn software(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.software)
+	}
+
+	/// System name from the seequencing software.
+	pub fn system_name(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.system_name)
+	}
+
+	/// System type.
+	pub fn system_type(&self) -> Result<&str, std::str::Utf8Error>
+	{
+		Ok(&self.inner.system_type)
+	}
+
+	/// Tracking id data.
+	pub fn tracking_id(&self) -> HashMap<String, String>
+	{
+		self.inner.tracking_id.clone()
+	}
+
+	/// Copy the run info into an owned [`RunInfoData`].
+	pub fn to_owned(&self) -> crate::error::Result<RunInfoData>
+	{
+		Ok(self.inner.clone())
+	}
+}
+
+impl fmt::Display for RunInfo
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		let run_info = &self.inner;
+
+		writeln!(f, "acquisition_id = {}", run_info.acquisition_id)?;
+		writeln!(
+			f,
+			"acquisition_start_time_ms = {}",
+			run_info.acquisition_start_time_ms
+		)?;
+		writeln!(f, "adc_max = {}", run_info.adc_max)?;
+		writeln!(f, "adc_min = {}", run_info.adc_min)?;
+		writeln!(f, "context_tags = {:?}", run_info.context_tags)?;
+		writeln!(f, "experiment_name = {}", run_info.experiment_name)?;
+		writeln!(f, "flow_cell_id = {}", run_info.flow_cell_id)?;
+		writeln!(f, "flow_cell_product_code = {}", run_info.flow_cell_product_code)?;
+		writeln!(f, "product_name = {}", run_info.protocol_name)?;
+		writeln!(f, "protocol_run_id = {}", run_info.protocol_run_id)?;
+		writeln!(f, "protocol_start_time_ms = {}", run_info.protocol_start_time_ms)?;
+		writeln!(f, "sample_id = {}", run_info.sample_id)?;
+		writeln!(f, "sample_rate = {}", run_info.sample_rate)?;
+		writeln!(f, "sequencing_kit = {}", run_info.sequencing_kit)?;
+		writeln!(f, "sequencer_position = {}", run_info.sequencer_position)?;
+		writeln!(f, "sequencer_position_type = {}", run_info.sequencer_position_type)?;
+		writeln!(f, "software = {}", run_info.software)?;
+		writeln!(f, "system_name = {}", run_info.system_name)?;
+		writeln!(f, "system_type = {}", run_info.system_type)?;
+		writeln!(f, "tracking_id = {:?}", run_info.tracking_id)
+	}
+}
+
+/// Iterator for run info metadata.
+/// See [`super::reader::Reader::run_info_iter`] for full usage.
+pub struct RunInfoIter<'a>
+{
+	pub(crate) reader: std::slice::Iter<'a, Arc<super::NativeReader>>,
+
+	pub(crate) current_row: usize,
+	pub(crate) current_reader: Option<&'a Arc<super::NativeReader>>,
+}
+
+impl<'a> Iterator for RunInfoIter<'a>
+{
+	type Item = crate::error::Result<RunInfo>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		loop
+		{
+			if let Some(reader) = self.current_reader
+			{
+				let run_infos = match reader.run_infos()
+				{
+					Ok(run_infos) => run_infos,
+					Err(e) =>
+					{
+						self.current_reader = None;
+						return Some(Err(e));
+					}
+				};
+
+				if let Some(run_info) = run_infos.get(self.current_row)
+				{
+					self.current_row += 1;
+					return Some(Ok(RunInfo {
+						inner: run_info.clone(),
+					}));
+				}
+			}
+
+			self.current_reader = Some(self.reader.next()?);
+			self.current_row = 0;
+		}
+	}
+}
@@ -4,6 +4,9 @@ use std::ffi::CStr;
 use std::ptr;
 use std::fmt;
 
+use crate::data::adc_to_pa;
+pub use crate::data::{ReadData, ReadDataBuilder, SignalRow};
+
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
@@ -33,6 +36,8 @@ impl<'a> Calibration<'a>
 	/// Obtain the digitisation field from the extra calibration data within the pod5.
 	pub fn digitisation(&mut self) -> u16
 	{
+		let _lock = crate::error::ffi_lock();
+
 		match self.digitisation
 		{
 			Some(value) => value,
@@ -45,7 +50,7 @@ impl<'a> Calibration<'a>
 
 				unsafe {
 					crate::pod5_ffi::pod5_get_calibration_extra_info(
-						self.inner.batch_record,
+						self.inner.batch.inner,
 						self.inner.batch_row,
 						&mut calibration_data,
 					);
@@ -62,6 +67,8 @@ impl<'a> Calibration<'a>
 	/// obtain the range field from the extra calibration data within the pod5.
 	pub fn range(&mut self) -> f32
 	{
+		let _lock = crate::error::ffi_lock();
+
 		match self.range
 		{
 			Some(value) => value,
@@ -74,7 +81,7 @@ impl<'a> Calibration<'a>
 
 				unsafe {
 					crate::pod5_ffi::pod5_get_calibration_extra_info(
-						self.inner.batch_record,
+						self.inner.batch.inner,
 						self.inner.batch_row,
 						&mut calibration_data,
 					);
@@ -89,8 +96,35 @@ impl<'a> Calibration<'a>
 	}
 }
 
+/// Signal row info handed out by the C API, which must be freed by it.
+/// The pointers are also needed to decode individual rows with `pod5_get_signal`.
+struct SignalRowPtrs
+{
+	inner: Vec<*mut crate::pod5_ffi::SignalRowInfo_t>,
+}
+
+impl Drop for SignalRowPtrs
+{
+	fn drop(&mut self)
+	{
+		let _lock = crate::error::ffi_lock();
+
+		if self.inner.iter().any(|row_info| row_info.is_null())
+		{
+			return;
+		}
+
+		unsafe {
+			crate::pod5_ffi::pod5_free_signal_row_info(self.inner.len(), self.inner.as_mut_ptr());
+		}
+	}
+}
+
 /// pod5 read information.
 /// see <https://github.com/nanoporetech/pod5-file-format/blob/master/docs/tables/reads.toml> for full pod5-file-format specification information.
+///
+/// A read keeps the batch it was read from loaded, so reads can be collected, sent to other
+/// threads and used after iteration has moved on.
 pub struct Read
 {
 	pub(crate) inner: crate::pod5_ffi::ReadBatchRowInfo_t,
@@ -98,38 +132,118 @@ pub struct Read
 
 	pub(crate) batch_row: usize,
 	pub(crate) reader: *mut crate::pod5_ffi::Pod5FileReader_t,
-	pub(crate) batch_record: *mut crate::pod5_ffi::Pod5ReadRecordBatch_t,
+	pub(crate) batch: std::sync::Arc<crate::readbatch::BatchHandle>,
 
 	pub(crate) has_compression: bool,
 }
 
+// SAFETY: the batch a read points into is only read from, and is kept loaded, along with its
+// reader, for as long as the read exists. Calls into the pod5 library are serialised by
+// crate::error::ffi_lock.
+unsafe impl Send for Read {}
+
 impl Read
 {
 	/// The uncompressed signal for the associated read.
 	pub fn signal(&self) -> crate::error::Result<Vec<i16>>
 	{
-		let mut signal_count: usize = 0;
-		let mut signal: Vec<i16>;
-		unsafe {
-			crate::pod5_ffi::pod5_get_read_complete_sample_count(
-				self.reader,
-				self.batch_record,
-				self.batch_row,
-				&mut signal_count,
-			);
+		let mut signal = Vec::new();
+		self.signal_into(&mut signal)?;
+		Ok(signal)
+	}
+
+	/// Write the uncompressed signal for the associated read into an existing buffer.
+	/// The buffer is resized to fit the signal, reusing its allocation where possible.
+	pub fn signal_into(&self, buffer: &mut Vec<i16>) -> crate::error::Result<()>
+	{
+		buffer.clear();
+		buffer.reserve(self.num_samples() as usize);
 
-			signal = vec![0; signal_count];
+		self.signal_chunks(|samples| {
+			buffer.extend_from_slice(samples);
+			Ok(())
+		})
+	}
 
-			crate::pod5_ffi::pod5_get_read_complete_signal(
-				self.reader,
-				self.batch_record,
-				self.batch_row,
+	/// Write the uncompressed signal for the associated read into the start of a slice.
+	///
+	/// # Returns
+	///
+	/// The number of samples written, or a capacity error if the slice is too small to hold them.
+	pub fn signal_into_slice(&self, buffer: &mut [i16]) -> crate::error::Result<usize>
+	{
+		let signal_count = self.num_samples() as usize;
+		if buffer.len() < signal_count
+		{
+			return Err(crate::error::Error::CapacityError(format!(
+				"signal has {} samples, buffer only holds {}",
 				signal_count,
-				signal.as_mut_ptr(),
-			);
+				buffer.len()
+			)));
 		}
 
-		crate::pod5_ok!(signal)
+		let mut written = 0;
+		self.signal_chunks(|samples| {
+			let end = written + samples.len();
+			if end > signal_count
+			{
+				return Err(crate::error::Error::CapacityError(format!(
+					"signal has more than the {} samples recorded for the read",
+					signal_count
+				)));
+			}
+
+			buffer[written..end].copy_from_slice(samples);
+			written = end;
+			Ok(())
+		})?;
+
+		Ok(written)
+	}
+
+	/// Decode each stored chunk of the read's signal in sample order, passing the samples to
+	/// `chunk`. Only looking up the chunks goes through the pod5 library, so the FFI lock is not
+	/// held while signal is decompressed.
+	fn signal_chunks<F>(&self, mut chunk: F) -> crate::error::Result<()>
+	where
+		F: FnMut(&[i16]) -> crate::error::Result<()>,
+	{
+		let row_infos = self.signal_row_info()?;
+
+		self.batch.reader.with_signal_table(|signal_table| {
+			for row_info in row_infos.iter()
+			{
+				chunk(&signal_table.samples(row_info.batch_index, row_info.batch_row_index)?)?;
+			}
+
+			Ok(())
+		})
+	}
+
+	/// The signal for the associated read, calibrated to picoamps.
+	pub fn signal_pa(&self) -> crate::error::Result<Vec<f32>>
+	{
+		let mut signal = Vec::new();
+		self.signal_pa_into(&mut signal)?;
+		Ok(signal)
+	}
+
+	/// Write the signal for the associated read, calibrated to picoamps, into an existing buffer.
+	/// The buffer is cleared first, so can be reused between reads.
+	pub fn signal_pa_into(&self, buffer: &mut Vec<f32>) -> crate::error::Result<()>
+	{
+		let calibration = self.calibration();
+		let (offset, scale) = (calibration.offset(), calibration.scale());
+
+		buffer.clear();
+		buffer.reserve(self.num_samples() as usize);
+
+		// Each stored chunk is converted as it is decompressed, so only one chunk of raw signal
+		// is held at a time.
+		self.signal_chunks(|samples| {
+			buffer.extend(samples.iter().map(|&sample| adc_to_pa(sample, offset, scale)));
+			Ok(())
+		})
 	}
 
 	/// The read id as a uuid.
@@ -177,15 +291,28 @@ impl Read
 	/// Attempts to get the pore type as a String.
 	pub fn pore_type_string(&self) -> crate::error::Result<String>
 	{
-		let mut c_string = vec![0i8; 10];
-		let mut str_length: usize = 10;
-		unsafe {
-			crate::pod5_ffi::pod5_get_pore_type(
-				self.batch_record,
-				self.pore_type(),
-				c_string.as_mut_ptr(),
-				&mut str_length,
-			);
+		let _lock = crate::error::ffi_lock();
+
+		// The library reports the size it needs when the buffer is too small for the name.
+		let mut c_string = vec![0i8; 32];
+		loop
+		{
+			let mut str_length = c_string.len();
+			let error = unsafe {
+				crate::pod5_ffi::pod5_get_pore_type(
+					self.batch.inner,
+					self.pore_type(),
+					c_string.as_mut_ptr(),
+					&mut str_length,
+				)
+			};
+
+			if error != crate::pod5_ffi::pod5_error_POD5_ERROR_STRING_NOT_LONG_ENOUGH
+			{
+				break;
+			}
+
+			c_string.resize(str_length.max(c_string.len() * 2), 0);
 		}
 
 		crate::pod5_ok!(unsafe { CStr::from_ptr(c_string.as_ptr()) }
@@ -224,6 +351,8 @@ impl Read
 	/// The runinfo struct from the meta data for the associated read.
 	pub fn run_info(&self) -> crate::error::Result<crate::runinfo::RunInfo>
 	{
+		let _lock = crate::error::ffi_lock();
+
 		let mut run_info = ptr::null_mut();
 
 		unsafe {
@@ -290,6 +419,232 @@ impl Read
 	{
 		self.inner.num_samples
 	}
+
+	/// Copy the read, including its signal, into an owned [`ReadData`] that can be edited and
+	/// written with a [`crate::writer::Writer`].
+	///
+	/// `pore_type` and `run_info` keep the indices used by the file being read, so must be
+	/// remapped with [`crate::writer::Writer::add_pore`] and
+	/// [`crate::writer::Writer::add_run_info`] before writing to a different file.
+	pub fn to_owned(&self) -> crate::error::Result<ReadData>
+	{
+		Ok(self.read_data(self.signal()?))
+	}
+
+	pub(crate) fn read_data(&self, signal: Vec<i16>) -> ReadData
+	{
+		let calibration = self.calibration();
+
+		ReadData {
+			read_id: self.uuid(),
+			read_number: self.read_number(),
+			start_sample: self.start_sample(),
+			median_before: self.median_before(),
+			channel: self.channel(),
+			well: self.well(),
+			pore_type: self.pore_type(),
+			calibration_offset: calibration.offset(),
+			calibration_scale: calibration.scale(),
+			end_reason: self.end_reason(),
+			end_reason_forced: self.end_reason_forced(),
+			run_info: self.run_info_num(),
+			num_minknow_events: self.num_minknow_events(),
+			tracked_scaling_scale: self.tracked_scaling_scale(),
+			tracked_scaling_shift: self.tracked_scaling_shift(),
+			predicted_scaling_scale: self.predicted_scaling_scale(),
+			predicted_scaling_shift: self.predicted_scaling_shift(),
+			num_reads_since_mux_change: self.num_reads_since_mux_change(),
+			time_since_mux_change: self.time_since_mux_change(),
+			signal,
+		}
+	}
+
+	/// Look up the signal table rows holding this read's signal, in sample order.
+	fn signal_row_ptrs(&self) -> crate::error::Result<SignalRowPtrs>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		let row_count = self.signal_row_count();
+		let mut signal_rows = vec![0u64; row_count as usize];
+
+		unsafe {
+			crate::pod5_ffi::pod5_get_signal_row_indices(
+				self.batch.inner,
+				self.batch_row,
+				row_count,
+				signal_rows.as_mut_ptr(),
+			);
+		}
+
+		crate::pod5_check_result!();
+
+		let mut row_infos = SignalRowPtrs {
+			inner: vec![ptr::null_mut(); signal_rows.len()],
+		};
+
+		unsafe {
+			crate::pod5_ffi::pod5_get_signal_row_info(
+				self.reader,
+				signal_rows.len(),
+				signal_rows.as_mut_ptr(),
+				row_infos.inner.as_mut_ptr(),
+			);
+		}
+
+		crate::pod5_ok!(row_infos)
+	}
+
+	pub(crate) fn signal_row_info(
+		&self,
+	) -> crate::error::Result<Vec<crate::pod5_ffi::SignalRowInfo_t>>
+	{
+		let row_infos = self.signal_row_ptrs()?;
+
+		Ok(row_infos
+			.inner
+			.iter()
+			.map(|row_info| unsafe { **row_info })
+			.collect())
+	}
+
+	/// Describes each stored chunk of the read's signal, in sample order.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// for read in reader.reads_iter(None)
+	/// {
+	///     let read = read?;
+	///     let rows = read.signal_rows()?;
+	///     let stored_bytes: usize = rows.iter().map(|row| row.byte_count).sum();
+	///     println!("{} chunks, {} bytes", rows.len(), stored_bytes);
+	/// }
+	/// ```
+	pub fn signal_rows(&self) -> crate::error::Result<Vec<SignalRow>>
+	{
+		let mut start_sample = 0;
+
+		Ok(self
+			.signal_row_info()?
+			.iter()
+			.map(|row_info| {
+				let row = SignalRow {
+					batch_index: row_info.batch_index,
+					batch_row_index: row_info.batch_row_index,
+					start_sample,
+					sample_count: row_info.stored_sample_count,
+					byte_count: row_info.stored_byte_count,
+				};
+				start_sample += row_info.stored_sample_count as u64;
+				row
+			})
+			.collect())
+	}
+
+	/// The uncompressed signal for a range of samples within the read.
+	///
+	/// Only the stored signal chunks overlapping the range are decompressed, so this is much
+	/// cheaper than [`Read::signal`] when only part of a long read is needed.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// for read in reader.reads_iter(None)
+	/// {
+	///     let read = read?;
+	///     let end = read.num_samples().min(4000) as usize;
+	///     let start_of_read = read.signal_range(0..end)?;
+	/// }
+	/// ```
+	pub fn signal_range(&self, range: std::ops::Range<usize>) -> crate::error::Result<Vec<i16>>
+	{
+		let num_samples = self.num_samples() as usize;
+		if range.start > range.end || range.end > num_samples
+		{
+			return Err(crate::error::Error::IndexError(format!(
+				"signal range {}..{} out of bounds for read with {} samples",
+				range.start, range.end, num_samples
+			)));
+		}
+
+		let mut signal = Vec::with_capacity(range.len());
+		if range.is_empty()
+		{
+			return Ok(signal);
+		}
+
+		let row_infos = self.signal_row_info()?;
+
+		self.batch.reader.with_signal_table(|signal_table| {
+			let mut chunk_start = 0;
+
+			for row_info in row_infos.iter()
+			{
+				let chunk_end = chunk_start + row_info.stored_sample_count as usize;
+
+				if chunk_end > range.start
+				{
+					let chunk =
+						signal_table.samples(row_info.batch_index, row_info.batch_row_index)?;
+					let from = range.start.saturating_sub(chunk_start);
+					let to = range.end.min(chunk_end) - chunk_start;
+					signal.extend_from_slice(&chunk[from..to]);
+				}
+
+				if chunk_end >= range.end
+				{
+					break;
+				}
+
+				chunk_start = chunk_end;
+			}
+
+			Ok(())
+		})?;
+
+		Ok(signal)
+	}
+
+	/// The VBZ compressed signal chunks for the read, as stored in the signal table.
+	pub(crate) fn compressed_signal(
+		&self,
+		signal_table: &mut crate::signaltable::SignalTable,
+	) -> crate::error::Result<Vec<SignalChunk>>
+	{
+		self.signal_row_info()?
+			.iter()
+			.map(|row_info| {
+				Ok(SignalChunk {
+					data: signal_table
+						.compressed_chunk(row_info.batch_index, row_info.batch_row_index)?,
+					sample_count: row_info.stored_sample_count,
+				})
+			})
+			.collect()
+	}
+}
+
+
+/// A single VBZ compressed section of a read's signal, see
+/// [`crate::writer::Writer::add_reads_pre_compressed`].
+#[derive(Debug, Clone, Default)]
+pub struct SignalChunk
+{
+	/// The VBZ compressed bytes.
+	pub data: Vec<u8>,
+	/// The number of samples once decompressed.
+	pub sample_count: u32,
+}
+
+impl SignalChunk
+{
+	/// Decompress the chunk without going through the pod5 C API, see [`crate::vbz`].
+	pub fn decompress(&self) -> crate::error::Result<Vec<i16>>
+	{
+		crate::vbz::decompress(&self.data, self.sample_count as usize)
+	}
 }
 
 impl fmt::Display for Read
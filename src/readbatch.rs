@@ -2,27 +2,243 @@
 
 use std::ptr;
 use std::ffi::c_void;
+#[cfg(feature = "polars")]
 use std::any::Any;
 
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use arrow::array::{
+	ArrayRef, BooleanArray, FixedSizeBinaryBuilder, Float32Array, Float32Builder, Int16Array,
+	Int16Builder, Int64Array, ListBuilder, RecordBatchOptions, StringDictionaryBuilder, UInt16Array,
+	UInt32Array, UInt64Array, UInt8Array,
+};
+use arrow::datatypes::{Field, Int16Type, Schema};
+use arrow::record_batch::RecordBatch;
+#[cfg(feature = "polars")]
 use indexmap::IndexMap;
 
 #[cfg(feature = "polars")]
 use polars::prelude::*;
 
-/// Record information, see [`BatchRecordIter`] for usage.
-pub struct BatchRecord
+pub use crate::data::DEFAULT_FIELDS;
+
+/// A loaded read batch, shared by its record and every read taken from it.
+/// The batch is freed once all of them have been dropped.
+pub(crate) struct BatchHandle
 {
 	pub(crate) inner: *mut crate::pod5_ffi::Pod5ReadRecordBatch_t,
+	pub(crate) reader: Arc<crate::reader::ReaderHandle>,
+}
 
-	#[cfg(feature = "polars")]
-	pub(crate) reader: *mut crate::pod5_ffi::Pod5FileReader_t,
+impl BatchHandle
+{
+	pub(crate) fn open(
+		reader: &crate::reader::InternalReader,
+		batch: usize,
+	) -> crate::error::Result<Arc<BatchHandle>>
+	{
+		let _lock = crate::error::ffi_lock();
 
-	#[cfg(feature = "polars")]
+		let mut batch_ptr = ptr::null_mut();
+		unsafe {
+			crate::pod5_ffi::pod5_get_read_batch(&mut batch_ptr, reader.inner, batch);
+		}
+
+		crate::pod5_ok!(Arc::new(BatchHandle {
+			inner: batch_ptr,
+			reader: reader.handle.clone(),
+		}))
+	}
+}
+
+// SAFETY: a loaded batch is only read from, and keeps the reader it was loaded from open.
+unsafe impl Send for BatchHandle {}
+unsafe impl Sync for BatchHandle {}
+
+impl Drop for BatchHandle
+{
+	fn drop(&mut self)
+	{
+		let _lock = crate::error::ffi_lock();
+
+		unsafe {
+			crate::pod5_ffi::pod5_free_read_batch(self.inner);
+		}
+	}
+}
+
+/// Loaded read batches keyed by file and batch, dropping the least recently used batch once
+/// full. Reads taken from a dropped batch keep it loaded for as long as they need it.
+pub(crate) struct BatchCache
+{
+	batches: VecDeque<((usize, usize), Arc<BatchHandle>)>,
+	capacity: usize,
+}
+
+impl BatchCache
+{
+	pub(crate) fn new(capacity: usize) -> BatchCache
+	{
+		BatchCache {
+			batches: VecDeque::new(),
+			capacity: capacity.max(1),
+		}
+	}
+
+	/// Keep at most `capacity` batches loaded, and always at least one.
+	pub(crate) fn set_capacity(&mut self, capacity: usize)
+	{
+		self.capacity = capacity.max(1);
+		while self.batches.len() > self.capacity
+		{
+			self.batches.pop_front();
+		}
+	}
+
+	/// Find a loaded batch, or load it, marking it as the most recently used.
+	pub(crate) fn get(
+		&mut self,
+		readers: &[crate::reader::InternalReader],
+		file: usize,
+		batch: usize,
+	) -> crate::error::Result<Arc<BatchHandle>>
+	{
+		let key = (file, batch);
+
+		match self.batches.iter().position(|(loaded, _)| *loaded == key)
+		{
+			Some(position) =>
+			{
+				let entry = self.batches.remove(position).unwrap();
+				self.batches.push_back(entry);
+			}
+			None =>
+			{
+				let handle = BatchHandle::open(&readers[file], batch)?;
+				if self.batches.len() == self.capacity
+				{
+					self.batches.pop_front();
+				}
+
+				self.batches.push_back((key, handle));
+			}
+		}
+
+		Ok(self.batches.back().unwrap().1.clone())
+	}
+}
+
+/// Record information, see [`BatchRecordIter`] for usage.
+pub struct BatchRecord
+{
+	pub(crate) batch: Arc<BatchHandle>,
 	pub(crate) fetch_path: Option<Vec<u32>>,
 }
 
 impl BatchRecord
 {
+	/// The rows of this batch to visit, either every row or only those being fetched.
+	pub(crate) fn rows(&self) -> Vec<u32>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		match &self.fetch_path
+		{
+			Some(path) => path.clone(),
+			None =>
+			{
+				let mut batch_rows: usize = 0;
+				unsafe {
+					crate::pod5_ffi::pod5_get_read_batch_row_count(&mut batch_rows, self.batch.inner);
+				}
+
+				(0..batch_rows as u32).collect()
+			}
+		}
+	}
+
+	pub(crate) fn read_at(&self, batch_row: usize) -> crate::read::Read
+	{
+		let _lock = crate::error::ffi_lock();
+
+		let mut read_ptr: crate::pod5_ffi::ReadBatchRowInfo_t = Default::default();
+		let mut table_ver: u16 = 0;
+		unsafe {
+			crate::pod5_ffi::pod5_get_read_batch_row_info_data(
+				self.batch.inner,
+				batch_row,
+				crate::pod5_ffi::READ_BATCH_ROW_INFO_VERSION as u16,
+				&mut read_ptr as *mut crate::pod5_ffi::ReadBatchRowInfo_t as *mut c_void,
+				&mut table_ver,
+			);
+		}
+
+		crate::read::Read {
+			inner: read_ptr,
+			table_ver,
+			batch_row,
+			reader: self.batch.reader.inner,
+			batch: self.batch.clone(),
+			has_compression: true,
+		}
+	}
+
+	/// The reads in this batch, either every read or only those being fetched.
+	pub fn reads(&self) -> impl Iterator<Item = crate::read::Read> + '_
+	{
+		self.rows().into_iter().map(|row| self.read_at(row as usize))
+	}
+
+	/// Create an Arrow record batch from a record consisting of reads.
+	///
+	/// Columns are typed to match the pod5 read table: `read_id` is a 16 byte fixed size binary,
+	/// while `pore_type`, `end_reason`, `run_info` (the acquisition id) and run info strings are
+	/// dictionary encoded.
+	///
+	/// # Arguements
+	///
+	/// * `fields` - Optional. Provide None for all fields or a vector of Strings for the fields to include.
+	///
+	/// Accepts the same fields as [`BatchRecord::to_df`], including `signal` and `signal_pa`.
+	/// The following run info fields can also be joined in for each read:
+	/// - acquisition_id
+	/// - acquisition_start_time_ms
+	/// - adc_max
+	/// - adc_min
+	/// - experiment_name
+	/// - flow_cell_id
+	/// - flow_cell_product_code
+	/// - protocol_name
+	/// - protocol_run_id
+	/// - protocol_start_time_ms
+	/// - sample_id
+	/// - sample_rate
+	/// - sequencing_kit
+	/// - sequencer_position
+	/// - sequencer_position_type
+	/// - software
+	/// - system_name
+	/// - system_type
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// for batch in reader.batch_records_iter(None)
+	/// {
+	///     let record_batch = batch?.to_arrow(&Some(vec!["read_id", "channel", "end_reason"]))?;
+	///     println!("{}", record_batch.num_rows());
+	/// }
+	/// ```
+	pub fn to_arrow(&self, fields: &Option<Vec<&str>>) -> crate::error::Result<RecordBatch>
+	{
+		let reads = self.reads().collect::<Vec<crate::read::Read>>();
+
+		reads_to_arrow(&reads, fields)
+	}
+
 	/// Create a Polars dataframe from a record consisting of reads.
 	/// Requires the polars feature to be enabled.
 	///
@@ -42,7 +258,7 @@ impl BatchRecord
 	/// - calibration_scale
 	/// - end_reason
 	/// - end_reason_forced
-	/// - run_info
+	/// - run_info (the acquisition id)
 	/// - num_minknow_events
 	/// - tracked_scaling_scale
 	/// - tracked_scaling_shift
@@ -52,57 +268,26 @@ impl BatchRecord
 	/// - time_since_mux_change
 	/// - signal_row_count
 	/// - num_samples
+	///
+	/// Signal is not included by default, but can be requested with the following fields:
+	/// - signal (raw samples as a `List<Int16>` column)
+	/// - signal_pa (calibrated picoampere samples as a `List<Float32>` column)
 	#[cfg(feature = "polars")]
 	pub fn to_df(&self, fields: &Option<Vec<&str>>) -> crate::error::Result<DataFrame>
 	{
 		let which_fields = match fields
 		{
 			Some(fields) => fields,
-			None => &vec![
-				"read_id",
-				"read_number",
-				"start_sample",
-				"median_before",
-				"channel",
-				"well",
-				"pore_type",
-				"calibration_offset",
-				"calibration_scale",
-				"end_reason",
-				"end_reason_forced",
-				"run_info",
-				"num_minknow_events",
-				"tracked_scaling_scale",
-				"tracked_scaling_shift",
-				"predicted_scaling_scale",
-				"predicted_scaling_shift",
-				"num_reads_since_mux_change",
-				"time_since_mux_change",
-				"signal_row_count",
-				"num_samples",
-			],
+			None => &DEFAULT_FIELDS.to_vec(),
 		};
 
 		let mut fields_set: IndexMap<&str, Vec<Box<dyn Any>>> =
 			IndexMap::with_capacity(which_fields.len());
 
-		let mut batch_rows: usize = 0;
-		let path_to_take = match &self.fetch_path
-		{
-			Some(path) =>
-			{
-				batch_rows = path.len();
-				path
-			}
-			None =>
-			{
-				unsafe {
-					crate::pod5_ffi::pod5_get_read_batch_row_count(&mut batch_rows, self.inner);
-				}
+		let mut run_infos: HashMap<i16, crate::runinfo::RunInfoData> = HashMap::new();
 
-				&(0..batch_rows as u32).collect::<Vec<u32>>()
-			}
-		};
+		let path_to_take = self.rows();
+		let batch_rows = path_to_take.len();
 
 		for field in which_fields
 		{
@@ -111,28 +296,7 @@ impl BatchRecord
 
 		for current_row in path_to_take
 		{
-			let current_row = *current_row as usize;
-
-			let mut read_ptr: crate::pod5_ffi::ReadBatchRowInfo_t = Default::default();
-			let mut table_ver: u16 = 0;
-			unsafe {
-				crate::pod5_ffi::pod5_get_read_batch_row_info_data(
-					self.inner,
-					current_row as usize,
-					crate::pod5_ffi::READ_BATCH_ROW_INFO_VERSION as u16,
-					&mut read_ptr as *mut crate::pod5_ffi::ReadBatchRowInfo_t as *mut c_void,
-					&mut table_ver,
-				);
-			}
-
-			let read_result = crate::read::Read {
-				inner: read_ptr,
-				table_ver,
-				batch_row: current_row,
-				reader: self.reader,
-				batch_record: self.inner,
-				has_compression: true,
-			};
+			let read_result = self.read_at(current_row as usize);
 
 			let calibration = read_result.calibration();
 
@@ -147,7 +311,11 @@ impl BatchRecord
 					"signal" => fields_set
 						.get_mut(field)
 						.unwrap()
-						.push(Box::new(read_result.signal().unwrap_or(Vec::new())) as Box<dyn Any>),
+						.push(Box::new(read_result.signal()?) as Box<dyn Any>),
+					"signal_pa" => fields_set
+						.get_mut(field)
+						.unwrap()
+						.push(Box::new(read_result.signal_pa()?) as Box<dyn Any>),
 					"read_number" => fields_set
 						.get_mut(field)
 						.unwrap()
@@ -187,11 +355,13 @@ impl BatchRecord
 					"end_reason_forced" => fields_set
 						.get_mut(field)
 						.unwrap()
-						.push(Box::new(read_result.end_reason_forced() as bool) as Box<dyn Any>),
+						.push(Box::new(read_result.end_reason_forced()) as Box<dyn Any>),
 					"run_info" => fields_set
 						.get_mut(field)
 						.unwrap()
-						.push(Box::new(read_result.run_info_num() as i32) as Box<dyn Any>),
+						.push(Box::new(
+							run_info_data(&mut run_infos, &read_result)?.acquisition_id.clone(),
+						) as Box<dyn Any>),
 					"num_minknow_events" => fields_set
 						.get_mut(field)
 						.unwrap()
@@ -246,19 +416,19 @@ impl BatchRecord
 			}
 			else if col_name == "signal"
 			{
-				/*let values: Vec<Vec<i32>> = data
+				let values: Vec<Series> = data
 					.into_iter()
-					.map(|v| *v.downcast::<Vec<i32>>().unwrap())
+					.map(|v| Series::new("".into(), *v.downcast::<Vec<i16>>().unwrap()))
 					.collect();
-				let slices: Vec<&[i32]> = values.iter().map(|v| v.as_slice()).collect();
-
-				// Create ListChunked directly
-				let list_chunked = ListChunked::new("", &slices);
-				let list_chunked = ListChunked::from_vec(
-					col_name.into(),
-					slices, // Convert Vec<Vec<i32>> to ListChunked
-				);
-				series.push(Series::new(col_name.into(), list_chunked));*/
+				series.push(Series::new(col_name.into(), values));
+			}
+			else if col_name == "signal_pa"
+			{
+				let values: Vec<Series> = data
+					.into_iter()
+					.map(|v| Series::new("".into(), *v.downcast::<Vec<f32>>().unwrap()))
+					.collect();
+				series.push(Series::new(col_name.into(), values));
 			}
 			else if col_name == "read_number"
 			{
@@ -342,9 +512,9 @@ impl BatchRecord
 			}
 			else if col_name == "run_info"
 			{
-				let values: Vec<i32> = data
+				let values: Vec<String> = data
 					.into_iter()
-					.map(|v| *v.downcast::<i32>().unwrap())
+					.map(|v| *v.downcast::<String>().unwrap())
 					.collect();
 				series.push(Series::new(col_name.into(), values));
 			}
@@ -434,18 +604,225 @@ impl BatchRecord
 	}
 }
 
-impl Drop for BatchRecord
+/// Build an Arrow record batch from reads, see [`BatchRecord::to_arrow`] for the accepted fields.
+pub(crate) fn reads_to_arrow(
+	reads: &[crate::read::Read],
+	fields: &Option<Vec<&str>>,
+) -> crate::error::Result<RecordBatch>
 {
-	fn drop(&mut self)
+	let which_fields = match fields
 	{
-		unsafe {
-			crate::pod5_ffi::pod5_free_read_batch(self.inner);
-		}
+		Some(fields) => fields.as_slice(),
+		None => &DEFAULT_FIELDS[..],
+	};
+
+	let mut run_infos: HashMap<i16, crate::runinfo::RunInfoData> = HashMap::new();
+	let mut schema_fields = Vec::with_capacity(which_fields.len());
+	let mut columns: Vec<ArrayRef> = Vec::with_capacity(which_fields.len());
+
+	for field in which_fields
+	{
+		let column: ArrayRef = match *field
+		{
+			"read_id" =>
+			{
+				let mut builder = FixedSizeBinaryBuilder::with_capacity(reads.len(), 16);
+				for read in reads
+				{
+					builder.append_value(read.uuid().as_bytes())?;
+				}
+				Arc::new(builder.finish())
+			}
+			"signal" =>
+			{
+				let mut builder = ListBuilder::new(Int16Builder::new());
+				for read in reads
+				{
+					builder.values().append_slice(&read.signal()?);
+					builder.append(true);
+				}
+				Arc::new(builder.finish())
+			}
+			"signal_pa" =>
+			{
+				let mut builder = ListBuilder::new(Float32Builder::new());
+				for read in reads
+				{
+					builder.values().append_slice(&read.signal_pa()?);
+					builder.append(true);
+				}
+				Arc::new(builder.finish())
+			}
+			"read_number" => Arc::new(UInt32Array::from_iter_values(
+				reads.iter().map(|read| read.read_number()),
+			)),
+			"start_sample" => Arc::new(UInt64Array::from_iter_values(
+				reads.iter().map(|read| read.start_sample()),
+			)),
+			"median_before" => Arc::new(Float32Array::from_iter_values(
+				reads.iter().map(|read| read.median_before()),
+			)),
+			"channel" => Arc::new(UInt16Array::from_iter_values(
+				reads.iter().map(|read| read.channel()),
+			)),
+			"well" => Arc::new(UInt8Array::from_iter_values(
+				reads.iter().map(|read| read.well()),
+			)),
+			"pore_type" =>
+			{
+				let mut pore_types: HashMap<i16, String> = HashMap::new();
+				let mut builder = StringDictionaryBuilder::<Int16Type>::new();
+				for read in reads
+				{
+					let pore_type = match pore_types.entry(read.pore_type())
+					{
+						Entry::Occupied(entry) => entry.into_mut(),
+						Entry::Vacant(entry) => entry.insert(read.pore_type_string()?),
+					};
+					builder.append(pore_type.as_str())?;
+				}
+				Arc::new(builder.finish())
+			}
+			"calibration_offset" => Arc::new(Float32Array::from_iter_values(
+				reads.iter().map(|read| read.calibration().offset()),
+			)),
+			"calibration_scale" => Arc::new(Float32Array::from_iter_values(
+				reads.iter().map(|read| read.calibration().scale()),
+			)),
+			"end_reason" =>
+			{
+				let mut builder = StringDictionaryBuilder::<Int16Type>::new();
+				for read in reads
+				{
+					builder.append(read.end_reason().to_string())?;
+				}
+				Arc::new(builder.finish())
+			}
+			"end_reason_forced" => Arc::new(BooleanArray::from_iter(
+				reads.iter().map(|read| Some(read.end_reason_forced())),
+			)),
+			"run_info" | "acquisition_id" | "experiment_name" | "flow_cell_id"
+			| "flow_cell_product_code" | "protocol_name" | "protocol_run_id" | "sample_id"
+			| "sequencing_kit" | "sequencer_position" | "sequencer_position_type" | "software"
+			| "system_name" | "system_type" =>
+			{
+				let mut builder = StringDictionaryBuilder::<Int16Type>::new();
+				for read in reads
+				{
+					let run_info = run_info_data(&mut run_infos, read)?;
+					builder.append(match *field
+					{
+						"experiment_name" => &run_info.experiment_name,
+						"flow_cell_id" => &run_info.flow_cell_id,
+						"flow_cell_product_code" => &run_info.flow_cell_product_code,
+						"protocol_name" => &run_info.protocol_name,
+						"protocol_run_id" => &run_info.protocol_run_id,
+						"sample_id" => &run_info.sample_id,
+						"sequencing_kit" => &run_info.sequencing_kit,
+						"sequencer_position" => &run_info.sequencer_position,
+						"sequencer_position_type" => &run_info.sequencer_position_type,
+						"software" => &run_info.software,
+						"system_name" => &run_info.system_name,
+						"system_type" => &run_info.system_type,
+						_ => &run_info.acquisition_id,
+					})?;
+				}
+				Arc::new(builder.finish())
+			}
+			"acquisition_start_time_ms" | "protocol_start_time_ms" =>
+			{
+				let mut values = Vec::with_capacity(reads.len());
+				for read in reads
+				{
+					let run_info = run_info_data(&mut run_infos, read)?;
+					values.push(match *field
+					{
+						"acquisition_start_time_ms" => run_info.acquisition_start_time_ms,
+						_ => run_info.protocol_start_time_ms,
+					});
+				}
+				Arc::new(Int64Array::from(values))
+			}
+			"adc_max" | "adc_min" =>
+			{
+				let mut values = Vec::with_capacity(reads.len());
+				for read in reads
+				{
+					let run_info = run_info_data(&mut run_infos, read)?;
+					values.push(match *field
+					{
+						"adc_max" => run_info.adc_max,
+						_ => run_info.adc_min,
+					});
+				}
+				Arc::new(Int16Array::from(values))
+			}
+			"sample_rate" =>
+			{
+				let mut values = Vec::with_capacity(reads.len());
+				for read in reads
+				{
+					values.push(run_info_data(&mut run_infos, read)?.sample_rate);
+				}
+				Arc::new(UInt16Array::from(values))
+			}
+			"num_minknow_events" => Arc::new(UInt64Array::from_iter_values(
+				reads.iter().map(|read| read.num_minknow_events()),
+			)),
+			"tracked_scaling_scale" => Arc::new(Float32Array::from_iter_values(
+				reads.iter().map(|read| read.tracked_scaling_scale()),
+			)),
+			"tracked_scaling_shift" => Arc::new(Float32Array::from_iter_values(
+				reads.iter().map(|read| read.tracked_scaling_shift()),
+			)),
+			"predicted_scaling_scale" => Arc::new(Float32Array::from_iter_values(
+				reads.iter().map(|read| read.predicted_scaling_scale()),
+			)),
+			"predicted_scaling_shift" => Arc::new(Float32Array::from_iter_values(
+				reads.iter().map(|read| read.predicted_scaling_shift()),
+			)),
+			"num_reads_since_mux_change" => Arc::new(UInt32Array::from_iter_values(
+				reads.iter().map(|read| read.num_reads_since_mux_change()),
+			)),
+			"time_since_mux_change" => Arc::new(Float32Array::from_iter_values(
+				reads.iter().map(|read| read.time_since_mux_change()),
+			)),
+			"signal_row_count" => Arc::new(Int64Array::from_iter_values(
+				reads.iter().map(|read| read.signal_row_count()),
+			)),
+			"num_samples" => Arc::new(UInt64Array::from_iter_values(
+				reads.iter().map(|read| read.num_samples()),
+			)),
+			_ => return Err(crate::error::Error::KeyError(format!("unknown field: {}", field))),
+		};
+
+		schema_fields.push(Field::new(*field, column.data_type().clone(), false));
+		columns.push(column);
 	}
+
+	let options = RecordBatchOptions::new().with_row_count(Some(reads.len()));
+	Ok(RecordBatch::try_new_with_options(
+		Arc::new(Schema::new(schema_fields)),
+		columns,
+		&options,
+	)?)
+}
+
+/// Run info for a read, converted once per run info index.
+fn run_info_data<'a>(
+	run_infos: &'a mut HashMap<i16, crate::runinfo::RunInfoData>,
+	read: &crate::read::Read,
+) -> crate::error::Result<&'a crate::runinfo::RunInfoData>
+{
+	Ok(match run_infos.entry(read.run_info_num())
+	{
+		Entry::Occupied(entry) => entry.into_mut(),
+		Entry::Vacant(entry) => entry.insert(read.run_info()?.to_owned()?),
+	})
 }
 
 /// Iterator for pod5 records.
-/// Currently only useful for creating Polars DataFrames from pod5 records.
+/// Useful for creating Arrow record batches or Polars DataFrames from pod5 records.
 /// # Example
 /// ````
 /// let reader = Reader::from_path("sample.pod5", None);
@@ -479,6 +856,8 @@ impl<'a> BatchRecordIter<'a>
 
 	fn next_batch(&mut self) -> Option<crate::error::Result<BatchRecord>>
 	{
+		let _lock = crate::error::ffi_lock();
+
 		self.reset_rows();
 
 		if self.rows == 0
@@ -497,33 +876,28 @@ impl<'a> BatchRecordIter<'a>
 			}
 		}
 
-		let mut batch_ptr = ptr::null_mut();
-		unsafe {
-			crate::pod5_ffi::pod5_get_read_batch(
-				&mut batch_ptr,
-				self.inner_reader.unwrap().inner,
-				self.current_row,
-			);
-		}
-
-		crate::pod5_check_error!();
+		let batch = match BatchHandle::open(self.inner_reader.unwrap(), self.current_row)
+		{
+			Ok(batch) => batch,
+			Err(e) => return Some(Err(e)),
+		};
 
 		let read_result = BatchRecord {
-			inner: batch_ptr,
-			reader: self.inner_reader.unwrap().inner,
+			batch,
 			fetch_path: None,
 		};
 
 		self.current_row += 1;
 
-		crate::pod5_ok!(Some, read_result)
+		Some(Ok(read_result))
 	}
 
 	fn next_fetch_batch(&mut self) -> Option<crate::error::Result<BatchRecord>>
 	{
 		self.reset_rows();
 
-		if self.rows == 0
+		// Move on to the next reader containing any of the requested reads
+		while self.rows == 0
 		{
 			self.inner_reader = match self.reader.next()
 			{
@@ -544,26 +918,20 @@ impl<'a> BatchRecordIter<'a>
 			return None;
 		};
 
-		let mut batch_ptr = ptr::null_mut();
-		unsafe {
-			crate::pod5_ffi::pod5_get_read_batch(
-				&mut batch_ptr,
-				self.inner_reader.unwrap().inner,
-				fetch_path[self.current_row].0,
-			);
-		}
-
-		crate::pod5_check_error!();
+		let batch = match BatchHandle::open(self.inner_reader.unwrap(), fetch_path[self.current_row].0)
+		{
+			Ok(batch) => batch,
+			Err(e) => return Some(Err(e)),
+		};
 
 		let read_result = BatchRecord {
-			inner: batch_ptr,
-			reader: self.inner_reader.unwrap().inner,
+			batch,
 			fetch_path: Some(fetch_path[self.current_row].1.clone()),
 		};
 
 		self.current_row += 1;
 
-		crate::pod5_ok!(Some, read_result)
+		Some(Ok(read_result))
 	}
 }
 
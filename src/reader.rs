@@ -1,24 +1,32 @@
-use std::{ffi::CString, ptr};
+use std::ffi::CString;
+use std::sync::{Arc, Mutex, OnceLock};
 
 use std::path::PathBuf;
-use std::io::{Read as StdRead, Seek, SeekFrom};
 use std::convert::TryFrom;
 
 pub use crate::reads::*;
 pub use crate::read::*;
 pub use crate::readbatch::*;
+pub use crate::data::FetchReport;
 
 #[cfg(feature = "polars")]
 use polars::prelude::*;
 
 #[cfg(feature = "recursive")]
 use ignore::{WalkBuilder, types::TypesBuilder};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+#[cfg(feature = "parquet")]
+use parquet::{arrow::ArrowWriter, basic::Compression, basic::ZstdLevel, file::properties::WriterProperties};
 use std::path::Path;
 
 /// Contains reader options, passed to Reader.
 pub struct ReaderOptions
 {
 	force_disable_file_mapping: bool,
+	index_files: bool,
 }
 
 impl ReaderOptions
@@ -29,9 +37,29 @@ impl ReaderOptions
 	{
 		ReaderOptions {
 			force_disable_file_mapping,
+			index_files: false,
 		}
 	}
 
+	/// Store the read id index used by [`Reader::get`] in a sidecar file next to each pod5 file,
+	/// e.g. `reads.pod5.idx`, and reuse it when the file is opened again.
+	///
+	/// Each sidecar records the file identifier, size and modification time of its pod5 file,
+	/// so an index left over from a different file, or from before the file was rewritten, is
+	/// detected and rebuilt.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let options = ReaderOptions::new(false).index_files(true);
+	/// let reader = Reader::from_path("/folder/containing/pod5files", options)?;
+	/// ```
+	pub fn index_files(mut self, index_files: bool) -> ReaderOptions
+	{
+		self.index_files = index_files;
+		self
+	}
+
 	pub(crate) fn to_ffi(&self) -> crate::pod5_ffi::Pod5ReaderOptions_t
 	{
 		crate::pod5_ffi::Pod5ReaderOptions_t {
@@ -40,16 +68,84 @@ impl ReaderOptions
 	}
 }
 
+
+/// Owns an open pod5 file reader, which is closed once its InternalReader and every batch
+/// loaded from it have been dropped.
+pub(crate) struct ReaderHandle
+{
+	pub(crate) inner: *mut crate::pod5_ffi::Pod5FileReader_t,
+
+	path: PathBuf,
+	signal_table_location: crate::pod5_ffi::EmbeddedFileData_t,
+	signal_tables: Mutex<Vec<crate::signaltable::SignalTable>>,
+}
+
+// SAFETY: see InternalReader.
+unsafe impl Send for ReaderHandle {}
+unsafe impl Sync for ReaderHandle {}
+
+impl ReaderHandle
+{
+	/// Run `f` with a signal table of this file, reusing one opened by an earlier call where
+	/// one is free.
+	///
+	/// The table is read directly rather than through the pod5 library, so signal can be
+	/// decompressed without holding the FFI lock.
+	pub(crate) fn with_signal_table<T, F>(&self, f: F) -> crate::error::Result<T>
+	where
+		F: FnOnce(&mut crate::signaltable::SignalTable) -> crate::error::Result<T>,
+	{
+		let free_table = self
+			.signal_tables
+			.lock()
+			.map_err(|e| crate::error::Error::UnknownError(e.to_string()))?
+			.pop();
+
+		let mut signal_table = match free_table
+		{
+			Some(signal_table) => signal_table,
+			None => crate::signaltable::SignalTable::open(&self.path, &self.signal_table_location)?,
+		};
+
+		let result = f(&mut signal_table);
+
+		if let Ok(mut signal_tables) = self.signal_tables.lock()
+		{
+			signal_tables.push(signal_table);
+		}
+
+		result
+	}
+}
+
+impl Drop for ReaderHandle
+{
+	fn drop(&mut self)
+	{
+		let _lock = crate::error::ffi_lock();
+
+		unsafe {
+			crate::pod5_ffi::pod5_close_and_free_reader(self.inner);
+			crate::pod5_ffi::pod5_terminate();
+		}
+	}
+}
+
 pub(crate) struct InternalReader
 {
 	pub(crate) inner: *mut crate::pod5_ffi::Pod5FileReader_t,
+	pub(crate) handle: Arc<ReaderHandle>,
 	pub(crate) has_compression: bool,
+	pub(crate) path: PathBuf,
+	pub(crate) signal_table_location: crate::pod5_ffi::EmbeddedFileData_t,
 }
 
 impl InternalReader
 {
 	pub(crate) fn count(&self) -> crate::error::Result<usize>
 	{
+		let _lock = crate::error::ffi_lock();
+
 		let mut read_count: usize = 0;
 		unsafe {
 			crate::pod5_ffi::pod5_get_read_count(self.inner, &mut read_count);
@@ -60,6 +156,8 @@ impl InternalReader
 
 	pub(crate) fn read_ids(&self) -> crate::error::Result<Vec<uuid::Uuid>>
 	{
+		let _lock = crate::error::ffi_lock();
+
 		let read_count = self.count()?;
 		let mut read_ids = vec![[0; 16]; read_count];
 		unsafe {
@@ -74,63 +172,104 @@ impl InternalReader
 		crate::pod5_ok!(read_ids)
 	}
 
-	fn detect_signal_compression<P: AsRef<Path>>(&mut self, path: P) -> crate::error::Result<()>
+	pub(crate) fn info(&self) -> crate::error::Result<crate::fileinfo::FileInfo>
 	{
-		let mut file_data: crate::pod5_ffi::EmbeddedFileData_t = Default::default();
+		let _lock = crate::error::ffi_lock();
+
+		let mut file_ptr: crate::pod5_ffi::FileInfo = Default::default();
 
 		unsafe {
-			crate::pod5_ffi::pod5_get_file_signal_table_location(self.inner, &mut file_data);
+			crate::pod5_ffi::pod5_get_file_info(self.inner, &mut file_ptr);
 		}
 
-		//let c_str = unsafe { CStr::from_ptr(file_data.file_name) };
-		//let str_slice = c_str.to_str().expect("Invalid UTF-8 sequence");
+		crate::pod5_ok!(crate::fileinfo::FileInfo { inner: file_ptr })
+	}
 
-		let mut file = std::fs::File::open(path)?;
+	pub(crate) fn batch_count(&self) -> crate::error::Result<usize>
+	{
+		let _lock = crate::error::ffi_lock();
 
-		file.seek(SeekFrom::Start(file_data.offset as u64))?;
+		let mut batch_count: usize = 0;
+		unsafe {
+			crate::pod5_ffi::pod5_get_read_batch_count(&mut batch_count, self.inner);
+		}
 
-		let mut buffer = vec![0u8; file_data.length];
-		file.read_exact(&mut buffer)?;
+		crate::pod5_ok!(batch_count)
+	}
 
-		let cursor = std::io::Cursor::new(buffer); // Wrap buffer in a Cursor for StreamReader
-		let reader = match arrow::ipc::reader::FileReader::try_new(cursor, None)
-		{
-			Ok(reader) => reader,
-			Err(_) =>
-			{
-				return Err(crate::error::Error::ArrowCompressionError(
-					"unable to determine signal compression".to_string(),
-				))
-			}
-		};
+	/// The row count of every read batch, taken from the batch metadata of the reads table so
+	/// that no read data is loaded.
+	pub(crate) fn batch_row_counts(&self) -> crate::error::Result<Vec<u64>>
+	{
+		let location = self.read_table_location()?;
+		let mut file = crate::signaltable::EmbeddedFile::open(
+			&self.path,
+			location.offset as u64,
+			location.length as u64,
+		)?;
+
+		crate::signaltable::batch_row_counts(&mut file)
+	}
 
-		let schema = reader.schema();
-		let signal_field = match schema.field_with_name("signal")
-		{
-			Ok(field) => field,
-			Err(_) =>
-			{
-				return Err(crate::error::Error::ArrowCompressionError(
-					"unable to determine signal compression".to_string(),
-				))
-			}
-		};
+	/// Every pore type in the file, in the order of the pore type indices reads refer to.
+	pub(crate) fn pore_types(&self) -> crate::error::Result<Vec<String>>
+	{
+		let location = self.read_table_location()?;
+
+		crate::signaltable::dictionary_values(
+			&self.path,
+			location.offset as u64,
+			location.length as u64,
+			"pore_type",
+		)
+	}
 
-		match signal_field.data_type()
-		{
-			arrow::datatypes::DataType::LargeBinary => self.has_compression = true,
-			_ => self.has_compression = false,
-		};
+	fn read_table_location(&self) -> crate::error::Result<crate::pod5_ffi::EmbeddedFileData_t>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		let mut location: crate::pod5_ffi::EmbeddedFileData_t = Default::default();
+		unsafe {
+			crate::pod5_ffi::pod5_get_file_read_table_location(self.inner, &mut location);
+		}
+
+		crate::pod5_ok!(location)
+	}
+
+	/// Load a read batch, visiting either every row or only the given rows.
+	pub(crate) fn read_batch(
+		&self,
+		batch: usize,
+		rows: Option<Vec<u32>>,
+	) -> crate::error::Result<BatchRecord>
+	{
+		Ok(BatchRecord {
+			batch: BatchHandle::open(self, batch)?,
+			fetch_path: rows,
+		})
+	}
+
+	fn detect_signal_compression(&mut self) -> crate::error::Result<()>
+	{
+		self.has_compression = self.signal_table()?.is_compressed()?;
 
 		Ok(())
 	}
 
+	/// Open the arrow signal table embedded within this pod5 file.
+	pub(crate) fn signal_table(&self) -> crate::error::Result<crate::signaltable::SignalTable>
+	{
+		crate::signaltable::SignalTable::open(&self.path, &self.signal_table_location)
+	}
+
 	pub(crate) fn get_fetch_path(
 		&self,
 		fetch: &Option<Vec<uuid::Uuid>>,
 		rows: &mut usize,
 	) -> Option<Vec<(usize, Vec<u32>)>>
 	{
+		let _lock = crate::error::ffi_lock();
+
 		match fetch
 		{
 			Some(fetch) =>
@@ -173,6 +312,7 @@ impl InternalReader
 						offset += size as usize;
 						(index, rows)
 					})
+					.filter(|(_, rows)| !rows.is_empty())
 					.collect::<Vec<(usize, Vec<u32>)>>();
 
 				*rows = batch_row_map.len();
@@ -184,17 +324,41 @@ impl InternalReader
 	}
 }
 
-impl Drop for InternalReader
+impl crate::index::IndexSource for InternalReader
 {
-	fn drop(&mut self)
+	fn path(&self) -> &Path
 	{
-		unsafe {
-			crate::pod5_ffi::pod5_close_and_free_reader(self.inner);
-			crate::pod5_ffi::pod5_terminate();
-		}
+		&self.path
+	}
+
+	fn file_identifier(&self) -> crate::error::Result<uuid::Uuid>
+	{
+		Ok(self.info()?.file_identifier())
+	}
+
+	fn count(&self) -> crate::error::Result<usize>
+	{
+		InternalReader::count(self)
+	}
+
+	fn read_ids(&self) -> crate::error::Result<Vec<uuid::Uuid>>
+	{
+		InternalReader::read_ids(self)
+	}
+
+	fn batch_row_counts(&self) -> crate::error::Result<Vec<u64>>
+	{
+		InternalReader::batch_row_counts(self)
 	}
 }
 
+// SAFETY: a pod5 file reader is only read from once opened, and the pod5 library supports
+// loading batches and signal from the same reader on several threads at once. Calls into the
+// library are serialised by crate::error::ffi_lock, as its error state is process wide. The reader
+// is freed only once its handle is dropped.
+unsafe impl Send for InternalReader {}
+unsafe impl Sync for InternalReader {}
+
 /// Open pod5 files, directories of pod5 files and iterate over records and reads.
 ///
 /// # Features
@@ -220,10 +384,26 @@ impl Drop for InternalReader
 pub struct Reader
 {
 	pub(crate) inner: Vec<InternalReader>,
+	index: OnceLock<crate::index::ReadIndex>,
+	index_files: bool,
+	batches: Mutex<BatchCache>,
 }
 
+/// The number of read batches [`Reader::get`] keeps loaded.
+const GET_BATCH_CACHE: usize = 4;
+
 impl Reader
 {
+	fn new(readers: Vec<InternalReader>, options: &Option<ReaderOptions>) -> Reader
+	{
+		Reader {
+			inner: readers,
+			index: OnceLock::new(),
+			index_files: options.as_ref().is_some_and(|options| options.index_files),
+			batches: Mutex::new(BatchCache::new(GET_BATCH_CACHE)),
+		}
+	}
+
 	/// Opens a pod5 file or directory of pod5 files for reading.
 	/// # Arguments
 	///
@@ -234,6 +414,8 @@ impl Reader
 		options: R,
 	) -> crate::error::Result<Reader>
 	{
+		let _lock = crate::error::ffi_lock();
+
 		let options = options.into();
 
 		unsafe {
@@ -251,7 +433,7 @@ impl Reader
 			readers.extend(Self::_readers_from_dir(path, &options)?);
 		}
 
-		let reader = Reader { inner: readers };
+		let reader = Reader::new(readers, &options);
 
 		crate::pod5_ok!(reader)
 	}
@@ -284,6 +466,8 @@ impl Reader
 		I: IntoIterator<Item = P>,
 		P: AsRef<Path>,
 	{
+		let _lock = crate::error::ffi_lock();
+
 		let options = options.into();
 
 		unsafe {
@@ -304,7 +488,7 @@ impl Reader
 			}
 		}
 
-		let reader = Reader { inner: readers };
+		let reader = Reader::new(readers, &options);
 
 		crate::pod5_ok!(reader)
 	}
@@ -314,6 +498,8 @@ impl Reader
 		options: &Option<ReaderOptions>,
 	) -> crate::error::Result<InternalReader>
 	{
+		let _lock = crate::error::ffi_lock();
+
 		let c_string = path
 			.as_ref()
 			.to_str()
@@ -333,11 +519,24 @@ impl Reader
 			unsafe { crate::pod5_ffi::pod5_open_file(c_string?.as_ptr()) },
 		};
 
+		let mut signal_table_location = Default::default();
+		unsafe {
+			crate::pod5_ffi::pod5_get_file_signal_table_location(ptr, &mut signal_table_location);
+		}
+
 		let mut reader = InternalReader {
 			inner: ptr,
+			handle: Arc::new(ReaderHandle {
+				inner: ptr,
+				path: path.as_ref().to_path_buf(),
+				signal_table_location,
+				signal_tables: Mutex::new(Vec::new()),
+			}),
 			has_compression: false,
+			path: path.as_ref().to_path_buf(),
+			signal_table_location,
 		};
-		reader.detect_signal_compression(path)?;
+		reader.detect_signal_compression()?;
 
 		crate::pod5_ok!(reader)
 	}
@@ -357,21 +556,13 @@ impl Reader
 
 		let mut results = Vec::new();
 
-		for result in walker
+		// Entries that can't be read are skipped.
+		for entry in walker.flatten()
 		{
-			match result
+			let path = entry.path();
+			if path.is_file()
 			{
-				Ok(entry) =>
-				{
-					let path = entry.path();
-					if path.is_file()
-					{
-						let reader = Self::_reader_from_file(path, &options)?;
-						results.push(reader);
-					}
-				}
-				Err(_) =>
-				{}
+				results.push(Self::_reader_from_file(path, options)?);
 			}
 		}
 
@@ -401,18 +592,7 @@ impl Reader
 	/// Returns a vector of all the file info structs found within the open pod5 files.
 	pub fn info(&self) -> Vec<crate::error::Result<crate::fileinfo::FileInfo>>
 	{
-		self.inner
-			.iter()
-			.map(|reader| {
-				let mut file_ptr: crate::pod5_ffi::FileInfo = Default::default();
-
-				unsafe {
-					crate::pod5_ffi::pod5_get_file_info(reader.inner, &mut file_ptr);
-				}
-
-				crate::pod5_ok!(crate::fileinfo::FileInfo { inner: file_ptr })
-			})
-			.collect()
+		self.inner.iter().map(|reader| reader.info()).collect()
 	}
 
 	/// Obtain the runinfo iterator.
@@ -449,36 +629,450 @@ impl Reader
 	/// A reads iterator.
 	pub fn reads_iter<U: Into<Option<Vec<uuid::Uuid>>>>(&self, fetch: U) -> Reads
 	{
-		let fetch = fetch.into();
+		Reads::new(&self.inner, fetch.into())
+	}
 
-		Reads {
-			reader: self.inner.iter(),
-			batch_count: 0,
-			batch_rows: 0,
-			current_batch: 0,
-			current_row: 0,
-			inner: ptr::null_mut(),
-			inner_reader: None,
-			fetch,
+	/// Obtain a single read by its read id.
+	///
+	/// An index of every read id in the open files is built the first time this is called, after
+	/// which each lookup only loads the read's batch. The most recently used batches are kept
+	/// loaded, so stepping between neighbouring reads does not reload their batch.
+	///
+	/// See [`ReaderOptions::index_files`] to keep the index on disk between runs.
+	///
+	/// # Arguments
+	///
+	/// * `read_id` - The read id to look up.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// if let Some(read) = reader.get(uuid!("002fde30-9e23-4125-9eae-d112c18a81a7"))?
+	/// {
+	///     println!("{} samples", read.num_samples());
+	/// }
+	/// ```
+	///
+	/// # Returns
+	///
+	/// The read, or None if no open file contains the read id.
+	pub fn get(&self, read_id: uuid::Uuid) -> crate::error::Result<Option<Read>>
+	{
+		let Some(location) = self.index()?.get(&read_id)
+		else
+		{
+			return Ok(None);
+		};
+
+		let batch = self
+			.batches
+			.lock()
+			.map_err(|e| crate::error::Error::UnknownError(e.to_string()))?
+			.get(&self.inner, location.file, location.batch)?;
+
+		let record = BatchRecord {
+			batch,
 			fetch_path: None,
+		};
+
+		let mut read = record.read_at(location.row);
+		read.has_compression = self.inner[location.file].has_compression;
+
+		Ok(Some(read))
+	}
+
+	/// Report which of the given read ids are found in the open files, and which are missing.
+	///
+	/// [`Reader::reads_iter`] skips read ids that are not found, so this can be used to check a
+	/// fetch up front. Uses the same index as [`Reader::get`].
+	///
+	/// # Arguments
+	///
+	/// * `fetch` - The read ids to look for.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// let read_ids = vec![uuid!("002fde30-9e23-4125-9eae-d112c18a81a7")];
+	/// let report = reader.fetch_report(&read_ids)?;
+	/// if !report.is_complete()
+	/// {
+	///     return Err(format!("missing reads: {:?}", report.missing()).into());
+	/// }
+	/// ```
+	///
+	/// # Returns
+	///
+	/// A [`FetchReport`], listing ids in the order they were requested.
+	pub fn fetch_report(&self, fetch: &[uuid::Uuid]) -> crate::error::Result<FetchReport>
+	{
+		let index = self.index()?;
+		let mut report = FetchReport::default();
+
+		for read_id in fetch
+		{
+			match index.get(read_id)
+			{
+				Some(location) => report
+					.found
+					.push((*read_id, self.inner[location.file].path.clone())),
+				None => report.missing.push(*read_id),
+			}
 		}
+
+		Ok(report)
 	}
 
-	//#[cfg(feature = "polars")]
-	//pub fn to_df(&self, fields: &Option<Vec<&str>>) -> crate::error::Result<DataFrame>
-	//{
-	//	Ok(self
-	//		.batch_records_iter()?
-	//		.map(|result| result.expect("REASON").to_df(&fields).expect("REASON"))
-	//		.reduce(|acc_df, next_df| {
-	//			acc_df
-	//				.clone()
-	//				.vstack_mut(&next_df)
-	//				.map(|_| acc_df)
-	//				.expect("REASON") // Combine vertically
-	//		})
-	//		.unwrap())
-	//}
+	pub(crate) fn index(&self) -> crate::error::Result<&crate::index::ReadIndex>
+	{
+		if let Some(index) = self.index.get()
+		{
+			return Ok(index);
+		}
+
+		let index = crate::index::ReadIndex::build(&self.inner, self.index_files)?;
+		Ok(self.index.get_or_init(|| index))
+	}
+
+	/// Create a parallel iterator over reads found within the open pod5 files.
+	/// Requires the rayon feature to be enabled.
+	///
+	/// Read batches from every open file are distributed over the rayon thread pool, and the
+	/// reads of each batch are visited on the thread that loaded it. Reads are not returned in
+	/// file order.
+	///
+	/// Calls into the pod5 library, such as loading batches and looking up where a read's signal
+	/// is stored, are serialised as the library keeps its error state in process wide globals.
+	/// Signal is read from the signal table and decompressed without the library, so decoding
+	/// signal, along with any other work done with each read, runs in parallel.
+	///
+	/// # Arguments
+	///
+	/// * `fetch` - Specify None to obtain all reads, or a vector of uuids for specific reads of interest
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// let total_samples = reader
+	///     .par_reads(None)
+	///     .map(|read| read.map(|read| read.num_samples()))
+	///     .sum::<Result<u64, _>>()?;
+	/// ```
+	///
+	/// # Returns
+	///
+	/// A rayon parallel iterator of reads.
+	#[cfg(feature = "rayon")]
+	pub fn par_reads<U: Into<Option<Vec<uuid::Uuid>>>>(
+		&self,
+		fetch: U,
+	) -> impl ParallelIterator<Item = crate::error::Result<Read>> + '_
+	{
+		let fetch = fetch.into();
+		let mut batches = Vec::new();
+		let mut errors = Vec::new();
+
+		for reader in self.inner.iter()
+		{
+			match &fetch
+			{
+				Some(_) =>
+				{
+					let mut batch_count: usize = 0;
+					if let Some(fetch_path) = reader.get_fetch_path(&fetch, &mut batch_count)
+					{
+						batches.extend(
+							fetch_path
+								.into_iter()
+								.map(|(batch, rows)| (reader, batch, Some(rows))),
+						);
+					}
+				}
+				None => match reader.batch_count()
+				{
+					Ok(batch_count) =>
+					{
+						batches.extend((0..batch_count).map(|batch| (reader, batch, None)))
+					}
+					Err(e) => errors.push(e),
+				},
+			}
+		}
+
+		errors.into_par_iter().map(Err).chain(
+			batches
+				.into_par_iter()
+				.flat_map_iter(|(reader, batch, rows)| BatchReads::new(reader, batch, rows)),
+		)
+	}
+
+	/// Create an iterator over specific reads, yielding them in the order they were requested.
+	///
+	/// [`Reader::reads_iter`] visits fetched reads in file order, whereas this looks each read up
+	/// through the index used by [`Reader::get`] and keeps a small number of read batches loaded,
+	/// see [`OrderedReads::batch_cache`]. A read id that is not found yields a `KeyError` in its
+	/// place, so results stay aligned with the requested ids.
+	///
+	/// # Arguments
+	///
+	/// * `fetch` - The read ids to obtain, in the order they should be returned.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// let read_ids = vec![
+	///     uuid!("002fde30-9e23-4125-9eae-d112c18a81a7"),
+	///     uuid!("000dab68-15a2-43c1-bc33-d9b2a3b4b69d"),
+	/// ];
+	/// for read in reader.reads_iter_ordered(read_ids).batch_cache(16)
+	/// {
+	///     let read = read?;
+	///     println!("{}", read.read_id()?);
+	/// }
+	/// ```
+	///
+	/// # Returns
+	///
+	/// An ordered reads iterator.
+	pub fn reads_iter_ordered(&self, fetch: Vec<uuid::Uuid>) -> OrderedReads<'_>
+	{
+		OrderedReads::new(self, fetch)
+	}
+
+	/// Create a Polars DataFrame over the reads from every open pod5 file.
+	/// Requires the polars feature to be enabled.
+	///
+	/// Each read is given a `source_file` column holding the path of the pod5 file it came from.
+	///
+	/// Every read batch is loaded into memory, so select only the `fields` needed to keep the
+	/// frame small. Call `.lazy()` on the result to continue with a Polars query.
+	///
+	/// # Arguments
+	///
+	/// * `fields` - Optional. Provide None for all fields, or the fields accepted by [`BatchRecord::to_df`].
+	/// * `run_info_fields` - Optional. Run info fields to join onto each read through its `run_info` acquisition id.
+	///
+	/// Accepted run info fields:
+	/// - acquisition_id
+	/// - acquisition_start_time_ms
+	/// - adc_max
+	/// - adc_min
+	/// - experiment_name
+	/// - flow_cell_id
+	/// - flow_cell_product_code
+	/// - protocol_name
+	/// - protocol_run_id
+	/// - protocol_start_time_ms
+	/// - sample_id
+	/// - sample_rate
+	/// - sequencing_kit
+	/// - sequencer_position
+	/// - sequencer_position_type
+	/// - software
+	/// - system_name
+	/// - system_type
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_vec(vec!["sample1.pod5", "sample2.pod5"], None)?;
+	/// let df = reader.to_df(
+	///     &Some(vec!["read_id", "channel", "num_samples"]),
+	///     &Some(vec!["flow_cell_id", "sample_id", "sequencing_kit"]),
+	/// )?;
+	/// ```
+	#[cfg(feature = "polars")]
+	pub fn to_df(
+		&self,
+		fields: &Option<Vec<&str>>,
+		run_info_fields: &Option<Vec<&str>>,
+	) -> crate::error::Result<DataFrame>
+	{
+		// The run info acquisition id is needed for the join, but is dropped again if it wasn't asked for.
+		let mut batch_fields = fields.clone();
+		let drop_run_info = match (&mut batch_fields, run_info_fields)
+		{
+			(Some(batch_fields), Some(_)) if !batch_fields.contains(&"run_info") =>
+			{
+				batch_fields.push("run_info");
+				true
+			}
+			_ => false,
+		};
+
+		let mut frames = Vec::with_capacity(self.inner.len());
+
+		for internal_reader in self.inner.iter()
+		{
+			let source_file = internal_reader.path.to_string_lossy().to_string();
+
+			let batches = BatchRecordIter {
+				reader: std::slice::from_ref(internal_reader).iter(),
+				rows: 0,
+				current_row: 0,
+				inner_reader: None,
+				fetch: None,
+				fetch_path: None,
+			};
+
+			let batch_frames = batches
+				.map(|batch| Ok(batch?.to_df(&batch_fields)?.lazy()))
+				.collect::<crate::error::Result<Vec<LazyFrame>>>()?;
+
+			if batch_frames.is_empty()
+			{
+				continue;
+			}
+
+			let mut frame = concat(batch_frames, UnionArgs::default())
+				.map_err(|e| crate::error::Error::UnknownError(e.to_string()))?
+				.with_column(lit(source_file).alias("source_file"));
+
+			if let Some(run_info_fields) = run_info_fields
+			{
+				let run_info_frame = Self::run_info_df(internal_reader, run_info_fields)?.lazy();
+				frame = frame.join(
+					run_info_frame,
+					[col("run_info")],
+					[col("run_info")],
+					JoinArgs::new(JoinType::Left),
+				);
+			}
+
+			frames.push(frame);
+		}
+
+		if frames.is_empty()
+		{
+			return Ok(DataFrame::empty());
+		}
+
+		let mut frame = concat(frames, UnionArgs::default())
+			.map_err(|e| crate::error::Error::UnknownError(e.to_string()))?;
+
+		if drop_run_info
+		{
+			frame = frame.drop(["run_info"]);
+		}
+
+		frame
+			.collect()
+			.map_err(|e| crate::error::Error::UnknownError(e.to_string()))
+	}
+
+	#[cfg(feature = "polars")]
+	fn run_info_df(
+		reader: &InternalReader,
+		run_info_fields: &[&str],
+	) -> crate::error::Result<DataFrame>
+	{
+		let run_infos = crate::runinfo::RunInfoIter {
+			rows: 0,
+			reader: std::slice::from_ref(reader).iter(),
+			current_row: 0,
+			current_reader: None,
+		}
+		.map(|run_info| run_info?.to_owned())
+		.collect::<crate::error::Result<Vec<crate::runinfo::RunInfoData>>>()?;
+
+		let mut series = vec![Series::new(
+			"run_info".into(),
+			run_infos.iter().map(|r| r.acquisition_id.clone()).collect::<Vec<String>>(),
+		)];
+
+		for field in run_info_fields
+		{
+			let name = (*field).into();
+			series.push(match *field
+			{
+				"acquisition_id" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.acquisition_id.clone()).collect::<Vec<String>>(),
+				),
+				"acquisition_start_time_ms" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.acquisition_start_time_ms).collect::<Vec<i64>>(),
+				),
+				"adc_max" => Series::new(name, run_infos.iter().map(|r| r.adc_max).collect::<Vec<i16>>()),
+				"adc_min" => Series::new(name, run_infos.iter().map(|r| r.adc_min).collect::<Vec<i16>>()),
+				"experiment_name" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.experiment_name.clone()).collect::<Vec<String>>(),
+				),
+				"flow_cell_id" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.flow_cell_id.clone()).collect::<Vec<String>>(),
+				),
+				"flow_cell_product_code" => Series::new(
+					name,
+					run_infos
+						.iter()
+						.map(|r| r.flow_cell_product_code.clone())
+						.collect::<Vec<String>>(),
+				),
+				"protocol_name" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.protocol_name.clone()).collect::<Vec<String>>(),
+				),
+				"protocol_run_id" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.protocol_run_id.clone()).collect::<Vec<String>>(),
+				),
+				"protocol_start_time_ms" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.protocol_start_time_ms).collect::<Vec<i64>>(),
+				),
+				"sample_id" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.sample_id.clone()).collect::<Vec<String>>(),
+				),
+				"sample_rate" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.sample_rate).collect::<Vec<u16>>(),
+				),
+				"sequencing_kit" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.sequencing_kit.clone()).collect::<Vec<String>>(),
+				),
+				"sequencer_position" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.sequencer_position.clone()).collect::<Vec<String>>(),
+				),
+				"sequencer_position_type" => Series::new(
+					name,
+					run_infos
+						.iter()
+						.map(|r| r.sequencer_position_type.clone())
+						.collect::<Vec<String>>(),
+				),
+				"software" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.software.clone()).collect::<Vec<String>>(),
+				),
+				"system_name" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.system_name.clone()).collect::<Vec<String>>(),
+				),
+				"system_type" => Series::new(
+					name,
+					run_infos.iter().map(|r| r.system_type.clone()).collect::<Vec<String>>(),
+				),
+				_ =>
+				{
+					return Err(crate::error::Error::KeyError(format!(
+						"unknown run info field: {}",
+						field
+					)))
+				}
+			});
+		}
+
+		DataFrame::new(series).map_err(|e| crate::error::Error::UnknownError(e.to_string()))
+	}
 
 	/// Obtain the batch records iterator.
 	/// Currently only useful for converting a record of reads to a Polars DataFrame.
@@ -500,6 +1094,118 @@ impl Reader
 		}
 	}
 
+	/// Write read metadata from all open pod5 files to a single Parquet file.
+	/// Requires the parquet feature to be enabled.
+	///
+	/// Each pod5 read batch is written as its own row group, and columns are typed as in
+	/// [`BatchRecord::to_arrow`].
+	///
+	/// # Arguments
+	///
+	/// * `path` - The path of the Parquet file to create.
+	/// * `fields` - Optional. Provide None for all read fields, or the fields to write. Run info fields such as `sample_id` are joined onto each read when requested.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// let fields = vec!["read_id", "channel", "end_reason", "num_samples", "sample_id"];
+	/// let count = reader.write_metadata_parquet("sample.parquet", &Some(fields))?;
+	/// ```
+	///
+	/// # Returns
+	///
+	/// The number of reads written.
+	#[cfg(feature = "parquet")]
+	pub fn write_metadata_parquet<P: AsRef<Path>>(
+		&self,
+		path: P,
+		fields: &Option<Vec<&str>>,
+	) -> crate::error::Result<usize>
+	{
+		let schema = crate::readbatch::reads_to_arrow(&[], fields)?.schema();
+		let properties = WriterProperties::builder()
+			.set_compression(Compression::ZSTD(ZstdLevel::default()))
+			.build();
+		let mut writer = ArrowWriter::try_new(std::fs::File::create(path)?, schema, Some(properties))?;
+		let mut count = 0;
+
+		for batch in self.batch_records_iter(None)
+		{
+			let record_batch = batch?.to_arrow(fields)?;
+			count += record_batch.num_rows();
+
+			writer.write(&record_batch)?;
+			writer.flush()?;
+		}
+
+		writer.close()?;
+
+		Ok(count)
+	}
+
+	/// Write every read matching a predicate to a new pod5 file.
+	///
+	/// All run info and pore types from the open files are carried over to the new file, whether
+	/// or not any matching read uses them. Signal is copied without being recompressed.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The path of the pod5 file to create.
+	/// * `options` - the [`crate::writer::WriterOptions`] object. Pass None to use the defaults (typical).
+	/// * `predicate` - Returns true for reads that should be written.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// let count = reader.filter_to_file("filtered.pod5", None, |read| {
+	///     (100..=200).contains(&read.channel())
+	///         && read.end_reason() == EndReason::SignalPositive
+	///         && read.num_samples() > 4000
+	/// })?;
+	/// ```
+	///
+	/// # Returns
+	///
+	/// The number of reads written.
+	pub fn filter_to_file<P, W, F>(
+		&self,
+		path: P,
+		options: W,
+		mut predicate: F,
+	) -> crate::error::Result<usize>
+	where
+		P: AsRef<Path>,
+		W: Into<Option<crate::writer::WriterOptions>>,
+		F: FnMut(&Read) -> bool,
+	{
+		let mut writer = crate::writer::Writer::from_path(path, options)?;
+		let mut count = 0;
+
+		for internal_reader in self.inner.iter()
+		{
+			let mut source = crate::writer::ReadSource::new(internal_reader)?;
+			let mut remap = crate::writer::IndexRemap::default();
+			writer.copy_run_infos(internal_reader, &mut remap)?;
+			writer.copy_pores(internal_reader, &mut remap)?;
+
+			for read in Reads::new(std::slice::from_ref(internal_reader), None)
+			{
+				let read = read?;
+				if predicate(&read)
+				{
+					writer.copy_read(&mut source, &mut remap, &read)?;
+					count += 1;
+				}
+			}
+		}
+
+		writer.close()?;
+
+		Ok(count)
+	}
+
 	//fn read(&self, read: &mut Read) -> bool
 	//{
 	//}
@@ -564,3 +1270,107 @@ impl TryFrom<Vec<String>> for Reader
 		Reader::from_iter(paths.into_iter().map(|p| PathBuf::from(p)), None)
 	}
 }
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use std::sync::mpsc;
+	use std::time::Duration;
+
+	/// The small V3 pod5 file also used by the native backend's tests.
+	fn test_file() -> PathBuf
+	{
+		Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/synthetic_v3.pod5")
+	}
+
+	#[test]
+	fn signal_decoded_without_ffi_lock()
+	{
+		let reader = Reader::from_path(test_file(), None).unwrap();
+		let reads = reader.reads_iter(None).collect::<crate::error::Result<Vec<Read>>>().unwrap();
+		let expected: Vec<Vec<i16>> = reads.iter().map(|read| read.signal().unwrap()).collect();
+		let row_infos: Vec<Vec<crate::pod5_ffi::SignalRowInfo_t>> =
+			reads.iter().map(|read| read.signal_row_info().unwrap()).collect();
+
+		// Decoding can only finish while this thread holds the lock if it never waits on it.
+		let _lock = crate::error::ffi_lock();
+		let (sender, receiver) = mpsc::channel();
+
+		for _ in 0..4
+		{
+			let handle = reader.inner[0].handle.clone();
+			let row_infos = row_infos.clone();
+			let sender = sender.clone();
+
+			std::thread::spawn(move || {
+				let signals = row_infos
+					.iter()
+					.map(|rows| {
+						handle.with_signal_table(|signal_table| {
+							let mut signal = Vec::new();
+							for row in rows.iter()
+							{
+								signal.extend(signal_table.samples(row.batch_index, row.batch_row_index)?);
+							}
+							Ok(signal)
+						})
+					})
+					.collect::<crate::error::Result<Vec<Vec<i16>>>>();
+
+				sender.send(signals).unwrap();
+			});
+		}
+
+		for _ in 0..4
+		{
+			let signals = receiver.recv_timeout(Duration::from_secs(30)).unwrap().unwrap();
+			assert_eq!(signals, expected);
+		}
+	}
+
+	#[test]
+	fn signal_methods_agree()
+	{
+		let reader = Reader::from_path(test_file(), None).unwrap();
+
+		for read in reader.reads_iter(None)
+		{
+			let read = read.unwrap();
+			let signal = read.signal().unwrap();
+			assert_eq!(signal.len() as u64, read.num_samples());
+
+			let mut buffer = vec![0; signal.len() + 1];
+			assert_eq!(read.signal_into_slice(&mut buffer).unwrap(), signal.len());
+			assert_eq!(&buffer[..signal.len()], &signal[..]);
+			assert!(read.signal_into_slice(&mut buffer[..signal.len() - 1]).is_err());
+
+			let range = 1..signal.len() - 1;
+			assert_eq!(read.signal_range(range.clone()).unwrap(), signal[range].to_vec());
+
+			assert_eq!(read.signal_pa().unwrap().len(), signal.len());
+		}
+	}
+
+	#[cfg(feature = "rayon")]
+	#[test]
+	fn par_reads_signal()
+	{
+		let reader = Reader::from_path(test_file(), None).unwrap();
+
+		let mut expected = reader
+			.reads_iter(None)
+			.map(|read| read.and_then(|read| Ok((read.uuid(), read.signal()?))))
+			.collect::<crate::error::Result<Vec<_>>>()
+			.unwrap();
+		let mut signals = reader
+			.par_reads(None)
+			.map(|read| read.and_then(|read| Ok((read.uuid(), read.signal()?))))
+			.collect::<crate::error::Result<Vec<_>>>()
+			.unwrap();
+
+		expected.sort();
+		signals.sort();
+		assert_eq!(signals, expected);
+	}
+}
@@ -1,4 +1,7 @@
-use std::{ffi::c_void, ptr};
+use std::ffi::c_void;
+use std::sync::{Arc, Mutex};
+
+pub use crate::data::SignalBuffer;
 
 /// Iterator for obtaining each read.
 /// See [`crate::reader::Reader::reads_iter`] for documentation.
@@ -12,7 +15,7 @@ pub struct Reads<'a>
 	pub(crate) current_row: usize,
 
 	pub(crate) inner_reader: Option<&'a crate::reader::InternalReader>,
-	pub(crate) inner: *mut crate::pod5_ffi::Pod5ReadRecordBatch_t,
+	pub(crate) inner: Option<Arc<crate::readbatch::BatchHandle>>,
 
 	pub(crate) fetch: Option<Vec<uuid::Uuid>>,
 	pub(crate) fetch_path: Option<Vec<(usize, Vec<u32>)>>,
@@ -20,29 +23,72 @@ pub struct Reads<'a>
 
 impl<'a> Reads<'a>
 {
+	pub(crate) fn new(
+		readers: &'a [crate::reader::InternalReader],
+		fetch: Option<Vec<uuid::Uuid>>,
+	) -> Reads<'a>
+	{
+		Reads {
+			reader: readers.iter(),
+			batch_count: 0,
+			batch_rows: 0,
+			current_batch: 0,
+			current_row: 0,
+			inner: None,
+			inner_reader: None,
+			fetch,
+			fetch_path: None,
+		}
+	}
+
+	/// Load the signal for every read as it is visited, reusing signal buffers once the
+	/// previous ones have been dropped.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// for result in reader.reads_iter(None).with_signal()
+	/// {
+	///     let (read, signal) = result?;
+	///     println!("{} has {} samples", read.uuid(), signal.len());
+	/// }
+	/// ```
+	pub fn with_signal(self) -> SignalReads<'a>
+	{
+		SignalReads {
+			reads: self,
+			pool: Arc::new(Mutex::new(Vec::new())),
+		}
+	}
+
 	fn reset_rows(&mut self)
 	{
-		// Clean up the previous batch if we've finished processing all rows
-		if self.current_row == self.batch_rows && !self.inner.is_null()
+		// Release the previous batch if we've finished processing all rows, reads taken from it
+		// keep it loaded for as long as they need it
+		if self.current_row == self.batch_rows && self.inner.is_some()
 		{
-			unsafe {
-				crate::pod5_ffi::pod5_free_read_batch(self.inner);
-				self.inner = ptr::null_mut();
-			}
-
-			self.batch_count = 0;
+			self.inner = None;
 			self.current_row = 0;
 			self.batch_rows = 0;
-			self.current_batch = 0;
+
+			// Move on to the next reader once every batch has been visited
+			if self.current_batch == self.batch_count
+			{
+				self.batch_count = 0;
+				self.current_batch = 0;
+			}
 		}
 	}
 
 	fn next_row(&mut self) -> Option<crate::error::Result<crate::read::Read>>
 	{
+		let _lock = crate::error::ffi_lock();
+
 		self.reset_rows();
 
-		// Process the next batch if we're on the first row of a batch
-		if self.batch_count == 0
+		// Process the next reader if we're on the first batch, skipping readers without batches
+		while self.batch_count == 0
 		{
 			// Try to fetch a new "reader" from the iterator
 			self.inner_reader = match self.reader.next()
@@ -61,36 +107,38 @@ impl<'a> Reads<'a>
 		}
 
 		// Start a new batch if we don't have an active one
-		if self.inner.is_null()
+		if self.inner.is_none()
 		{
-			unsafe {
-				crate::pod5_ffi::pod5_get_read_batch(
-					&mut self.inner,
-					self.inner_reader.as_ref().unwrap().inner,
-					self.current_batch,
-				);
-			}
-
-			crate::pod5_check_error!();
+			let batch = match crate::readbatch::BatchHandle::open(
+				self.inner_reader.as_ref().unwrap(),
+				self.current_batch,
+			)
+			{
+				Ok(batch) => batch,
+				Err(e) => return Some(Err(e)),
+			};
 
 			self.current_batch += 1;
 
 			// Get the number of rows in the current batch
 			let mut batch_rows: usize = 0;
 			unsafe {
-				crate::pod5_ffi::pod5_get_read_batch_row_count(&mut batch_rows, self.inner);
+				crate::pod5_ffi::pod5_get_read_batch_row_count(&mut batch_rows, batch.inner);
 			}
 
 			crate::pod5_check_error!();
 			self.batch_rows = batch_rows;
+			self.inner = Some(batch);
 		}
 
+		let batch = self.inner.as_ref().unwrap();
+
 		// Fetch the next row of data from the current batch
 		let mut read_ptr: crate::pod5_ffi::ReadBatchRowInfo_t = Default::default();
 		let mut table_ver: u16 = 0;
 		unsafe {
 			crate::pod5_ffi::pod5_get_read_batch_row_info_data(
-				self.inner,
+				batch.inner,
 				self.current_row,
 				crate::pod5_ffi::READ_BATCH_ROW_INFO_VERSION as u16,
 				&mut read_ptr as *mut crate::pod5_ffi::ReadBatchRowInfo_t as *mut c_void,
@@ -104,7 +152,7 @@ impl<'a> Reads<'a>
 			table_ver,
 			batch_row: self.current_row,
 			reader: self.inner_reader.as_ref().unwrap().inner,
-			batch_record: self.inner,
+			batch: batch.clone(),
 			has_compression: self.inner_reader.as_ref().unwrap().has_compression,
 		};
 
@@ -117,9 +165,12 @@ impl<'a> Reads<'a>
 
 	fn next_fetch_row(&mut self) -> Option<crate::error::Result<crate::read::Read>>
 	{
+		let _lock = crate::error::ffi_lock();
+
 		self.reset_rows();
 
-		if self.batch_count == 0
+		// Move on to the next reader containing any of the requested reads
+		while self.batch_count == 0
 		{
 			// Try to fetch a new "reader" from the iterator
 			self.inner_reader = match self.reader.next()
@@ -145,28 +196,31 @@ impl<'a> Reads<'a>
 		};
 
 		// Start a new batch if we don't have an active one
-		if self.inner.is_null()
+		if self.inner.is_none()
 		{
-			unsafe {
-				crate::pod5_ffi::pod5_get_read_batch(
-					&mut self.inner,
-					self.inner_reader.as_ref().unwrap().inner,
-					fetch_path[self.current_batch].0,
-				);
-			}
+			let batch = match crate::readbatch::BatchHandle::open(
+				self.inner_reader.as_ref().unwrap(),
+				fetch_path[self.current_batch].0,
+			)
+			{
+				Ok(batch) => batch,
+				Err(e) => return Some(Err(e)),
+			};
 
-			crate::pod5_check_error!();
+			self.inner = Some(batch);
 			self.batch_rows = fetch_path[self.current_batch].1.len();
 
 			self.current_batch += 1;
 		}
 
+		let batch = self.inner.as_ref().unwrap();
+
 		// Fetch the next row of data from the current batch
 		let mut read_ptr: crate::pod5_ffi::ReadBatchRowInfo_t = Default::default();
 		let mut table_ver: u16 = 0;
 		unsafe {
 			crate::pod5_ffi::pod5_get_read_batch_row_info_data(
-				self.inner,
+				batch.inner,
 				fetch_path[self.current_batch - 1].1[self.current_row] as usize,
 				crate::pod5_ffi::READ_BATCH_ROW_INFO_VERSION as u16,
 				&mut read_ptr as *mut crate::pod5_ffi::ReadBatchRowInfo_t as *mut c_void,
@@ -180,7 +234,7 @@ impl<'a> Reads<'a>
 			table_ver,
 			batch_row: fetch_path[self.current_batch - 1].1[self.current_row] as usize,
 			reader: self.inner_reader.as_ref().unwrap().inner,
-			batch_record: self.inner,
+			batch: batch.clone(),
 			has_compression: self.inner_reader.as_ref().unwrap().has_compression,
 		};
 
@@ -205,3 +259,175 @@ impl<'a> Iterator for Reads<'a>
 		}
 	}
 }
+
+
+/// Iterator for obtaining each read along with its signal.
+/// See [`Reads::with_signal`] for documentation.
+pub struct SignalReads<'a>
+{
+	reads: Reads<'a>,
+	pool: Arc<Mutex<Vec<Vec<i16>>>>,
+}
+
+impl<'a> Iterator for SignalReads<'a>
+{
+	type Item = crate::error::Result<(crate::read::Read, SignalBuffer)>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		let read = match self.reads.next()?
+		{
+			Ok(read) => read,
+			Err(e) => return Some(Err(e)),
+		};
+
+		let mut signal = SignalBuffer::from_pool(&self.pool);
+
+		match read.signal_into(&mut signal)
+		{
+			Ok(()) => Some(Ok((read, signal))),
+			Err(e) => Some(Err(e)),
+		}
+	}
+}
+
+/// Number of read batches kept loaded by [`OrderedReads`] unless set with
+/// [`OrderedReads::batch_cache`].
+const ORDERED_BATCH_CACHE: usize = 8;
+
+/// Iterator for obtaining reads in the order they were requested.
+/// See [`crate::reader::Reader::reads_iter_ordered`] for documentation.
+pub struct OrderedReads<'a>
+{
+	reader: &'a crate::reader::Reader,
+	fetch: std::vec::IntoIter<uuid::Uuid>,
+	batches: crate::readbatch::BatchCache,
+}
+
+impl<'a> OrderedReads<'a>
+{
+	pub(crate) fn new(reader: &'a crate::reader::Reader, fetch: Vec<uuid::Uuid>) -> OrderedReads<'a>
+	{
+		OrderedReads {
+			reader,
+			fetch: fetch.into_iter(),
+			batches: crate::readbatch::BatchCache::new(ORDERED_BATCH_CACHE),
+		}
+	}
+
+	/// Set how many read batches are kept loaded, dropping the least recently used batch once
+	/// the limit is reached. Defaults to 8, and at least one batch is always kept. Reads that
+	/// have already been returned keep their batch loaded regardless.
+	pub fn batch_cache(mut self, batches: usize) -> OrderedReads<'a>
+	{
+		self.batches.set_capacity(batches);
+		self
+	}
+}
+
+impl<'a> Iterator for OrderedReads<'a>
+{
+	type Item = crate::error::Result<crate::read::Read>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		let read_id = self.fetch.next()?;
+
+		let location = match self.reader.index()
+		{
+			Ok(index) => index.get(&read_id),
+			Err(e) => return Some(Err(e)),
+		};
+
+		let Some(location) = location
+		else
+		{
+			return Some(Err(crate::error::Error::KeyError(format!(
+				"read id not found: {}",
+				read_id
+			))));
+		};
+
+		let batch = match self.batches.get(&self.reader.inner, location.file, location.batch)
+		{
+			Ok(batch) => batch,
+			Err(e) => return Some(Err(e)),
+		};
+
+		let record = crate::readbatch::BatchRecord {
+			batch,
+			fetch_path: None,
+		};
+
+		let mut read = record.read_at(location.row);
+		read.has_compression = self.reader.inner[location.file].has_compression;
+
+		Some(Ok(read))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>)
+	{
+		self.fetch.size_hint()
+	}
+}
+
+/// Iterator over the reads of a single read batch.
+/// See [`crate::reader::Reader::par_reads`] for documentation.
+#[cfg(feature = "rayon")]
+pub struct BatchReads
+{
+	record: Option<crate::readbatch::BatchRecord>,
+	error: Option<crate::error::Error>,
+	rows: std::vec::IntoIter<u32>,
+	has_compression: bool,
+}
+
+#[cfg(feature = "rayon")]
+impl BatchReads
+{
+	pub(crate) fn new(
+		reader: &crate::reader::InternalReader,
+		batch: usize,
+		rows: Option<Vec<u32>>,
+	) -> BatchReads
+	{
+		let mut batch_reads = BatchReads {
+			record: None,
+			error: None,
+			rows: Vec::new().into_iter(),
+			has_compression: reader.has_compression,
+		};
+
+		match reader.read_batch(batch, rows)
+		{
+			Ok(record) =>
+			{
+				batch_reads.rows = record.rows().into_iter();
+				batch_reads.record = Some(record);
+			}
+			Err(e) => batch_reads.error = Some(e),
+		}
+
+		batch_reads
+	}
+}
+
+#[cfg(feature = "rayon")]
+impl Iterator for BatchReads
+{
+	type Item = crate::error::Result<crate::read::Read>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		if let Some(e) = self.error.take()
+		{
+			return Some(Err(e));
+		}
+
+		let record = self.record.as_ref()?;
+		let mut read = record.read_at(self.rows.next()? as usize);
+		read.has_compression = self.has_compression;
+
+		Some(Ok(read))
+	}
+}
@@ -3,6 +3,8 @@ use std::fmt;
 use std::ptr;
 use std::collections::HashMap;
 
+pub use crate::data::RunInfoData;
+
 /// Run information metadata from the pod5 such as experiment name or flowcell ID.
 /// Obtained either by the [`crate::reader::Reader::run_info_iter`] function for all run info objects,
 /// or by calling [`crate::reader::Read::run_info`] directly on a read.
@@ -214,6 +216,34 @@ impl RunInfo
 			.collect()
 	}
 
+	/// Copy the run info into an owned [`RunInfoData`] that can be written with a
+	/// [`crate::writer::Writer`].
+	pub fn to_owned(&self) -> crate::error::Result<RunInfoData>
+	{
+		Ok(RunInfoData {
+			acquisition_id: self.acquisition_id()?.to_string(),
+			acquisition_start_time_ms: self.acquisition_start_time_ms(),
+			adc_max: self.adc_max(),
+			adc_min: self.adc_min(),
+			context_tags: self.context_tags(),
+			experiment_name: self.experiment_name()?.to_string(),
+			flow_cell_id: self.flow_cell_id()?.to_string(),
+			flow_cell_product_code: self.flow_cell_product_code()?.to_string(),
+			protocol_name: self.protocol_name()?.to_string(),
+			protocol_run_id: self.protocol_run_id()?.to_string(),
+			protocol_start_time_ms: self.protocol_start_time_ms(),
+			sample_id: self.sample_id()?.to_string(),
+			sample_rate: self.sample_rate(),
+			sequencing_kit: self.sequencing_kit()?.to_string(),
+			sequencer_position: self.sequencer_position()?.to_string(),
+			sequencer_position_type: self.sequencer_position_type()?.to_string(),
+			software: self.software()?.to_string(),
+			system_name: self.system_name()?.to_string(),
+			system_type: self.system_type()?.to_string(),
+			tracking_id: self.tracking_id(),
+		})
+	}
+
 	fn handle_result<T: fmt::Display, E: fmt::Debug>(result: Result<T, E>) -> String
 	{
 		result
@@ -309,6 +339,8 @@ impl Drop for RunInfo
 {
 	fn drop(&mut self)
 	{
+		let _lock = crate::error::ffi_lock();
+
 		unsafe {
 			crate::pod5_ffi::pod5_free_run_info(self.inner);
 		}
@@ -332,6 +364,8 @@ impl<'a> Iterator for RunInfoIter<'a>
 
 	fn next(&mut self) -> Option<Self::Item>
 	{
+		let _lock = crate::error::ffi_lock();
+
 		if self.current_row == self.rows
 		{
 			self.current_row = 0;
@@ -0,0 +1,394 @@
+use std::fs::File;
+use std::io::{BufReader, Read as StdRead, Seek, SeekFrom};
+use std::path::Path;
+
+use arrow::array::{Array, AsArray};
+use arrow::datatypes::{Int16Type, UInt32Type};
+use arrow::error::ArrowError;
+use arrow::ipc::reader::{read_footer_length, FileReader};
+use arrow::ipc::{root_as_footer, root_as_message};
+use arrow::record_batch::RecordBatch;
+
+/// A window onto an arrow file embedded within a pod5 file.
+pub(crate) struct EmbeddedFile
+{
+	file: File,
+	offset: u64,
+	length: u64,
+	position: u64,
+}
+
+impl EmbeddedFile
+{
+	pub(crate) fn open<P: AsRef<Path>>(
+		path: P,
+		offset: u64,
+		length: u64,
+	) -> crate::error::Result<EmbeddedFile>
+	{
+		let mut file = File::open(path)?;
+		file.seek(SeekFrom::Start(offset))?;
+
+		Ok(EmbeddedFile {
+			file,
+			offset,
+			length,
+			position: 0,
+		})
+	}
+}
+
+impl StdRead for EmbeddedFile
+{
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize>
+	{
+		let remaining = self.length.saturating_sub(self.position) as usize;
+		let to_read = buf.len().min(remaining);
+		let read = self.file.read(&mut buf[..to_read])?;
+		self.position += read as u64;
+		Ok(read)
+	}
+}
+
+impl Seek for EmbeddedFile
+{
+	fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64>
+	{
+		let position = match pos
+		{
+			SeekFrom::Start(position) => position as i64,
+			SeekFrom::End(delta) => self.length as i64 + delta,
+			SeekFrom::Current(delta) => self.position as i64 + delta,
+		};
+
+		if position < 0
+		{
+			return Err(std::io::Error::new(
+				std::io::ErrorKind::InvalidInput,
+				"seek before start of embedded file",
+			));
+		}
+
+		self.file.seek(SeekFrom::Start(self.offset + position as u64))?;
+		self.position = position as u64;
+		Ok(self.position)
+	}
+}
+
+/// Marks the start of an arrow IPC message, ahead of its metadata length.
+const CONTINUATION_MARKER: [u8; 4] = [0xff; 4];
+
+/// The row count of every record batch in an embedded arrow file, read from each batch's
+/// metadata without loading its columns.
+pub(crate) fn batch_row_counts(file: &mut EmbeddedFile) -> crate::error::Result<Vec<u64>>
+{
+	let mut trailer = [0u8; 10];
+	file.seek(SeekFrom::End(-10))?;
+	file.read_exact(&mut trailer)?;
+
+	let footer_length = read_footer_length(trailer)?;
+	let mut footer = vec![0u8; footer_length];
+	file.seek(SeekFrom::End(-10 - footer_length as i64))?;
+	file.read_exact(&mut footer)?;
+
+	let footer = root_as_footer(&footer).map_err(|e| {
+		ArrowError::ParseError(format!("unable to read arrow file footer: {:?}", e))
+	})?;
+	let blocks = footer.recordBatches().map(|blocks| blocks.iter().copied().collect::<Vec<_>>());
+
+	let mut row_counts = Vec::new();
+	for block in blocks.unwrap_or_default()
+	{
+		let mut metadata = vec![0u8; block.metaDataLength() as usize];
+		file.seek(SeekFrom::Start(block.offset() as u64))?;
+		file.read_exact(&mut metadata)?;
+
+		if metadata.len() < 8
+		{
+			return Err(ArrowError::ParseError("arrow batch metadata too short".to_string()).into());
+		}
+
+		let message = match metadata[..4] == CONTINUATION_MARKER
+		{
+			true => &metadata[8..],
+			false => &metadata[4..],
+		};
+
+		let message = root_as_message(message).map_err(|e| {
+			ArrowError::ParseError(format!("unable to read arrow message: {:?}", e))
+		})?;
+		let record_batch = message.header_as_record_batch().ok_or_else(|| {
+			ArrowError::ParseError("arrow message is not a record batch".to_string())
+		})?;
+
+		row_counts.push(record_batch.length() as u64);
+	}
+
+	Ok(row_counts)
+}
+
+/// The values of a dictionary encoded string column in an embedded arrow file, in dictionary
+/// index order. Only that column is decoded.
+#[cfg(feature = "ffi")]
+pub(crate) fn dictionary_values<P: AsRef<Path>>(
+	path: P,
+	offset: u64,
+	length: u64,
+	column: &str,
+) -> crate::error::Result<Vec<String>>
+{
+	let file = EmbeddedFile::open(&path, offset, length)?;
+	let column_index = FileReader::try_new_buffered(file, None)?.schema().index_of(column)?;
+
+	let file = EmbeddedFile::open(&path, offset, length)?;
+	let reader = FileReader::try_new_buffered(file, Some(vec![column_index]))?;
+
+	let column_error =
+		|| crate::error::Error::TypeError(format!("{} is not a dictionary of strings", column));
+
+	let mut values: Vec<String> = Vec::new();
+	for batch in reader
+	{
+		let batch = batch?;
+		let dictionary = batch
+			.column(0)
+			.as_dictionary_opt::<Int16Type>()
+			.ok_or_else(column_error)?;
+		let strings = dictionary
+			.values()
+			.as_string_opt::<i32>()
+			.ok_or_else(column_error)?;
+
+		// Dictionaries are only ever appended to, so later batches hold every earlier value.
+		for value in strings.iter().skip(values.len())
+		{
+			values.push(value.unwrap_or_default().to_string());
+		}
+	}
+
+	Ok(values)
+}
+
+/// Direct access to the arrow signal table of a pod5 file, used to read stored chunks and
+/// decompress signal without going through the C API.
+pub(crate) struct SignalTable
+{
+	reader: FileReader<BufReader<EmbeddedFile>>,
+	current_batch: Option<(usize, RecordBatch)>,
+
+	#[cfg(feature = "native")]
+	batch_offsets: Vec<u64>,
+}
+
+impl SignalTable
+{
+	#[cfg(feature = "ffi")]
+	pub(crate) fn open<P: AsRef<Path>>(
+		path: P,
+		file_data: &crate::pod5_ffi::EmbeddedFileData_t,
+	) -> crate::error::Result<SignalTable>
+	{
+		Self::open_at(path, file_data.offset as u64, file_data.length as u64)
+	}
+
+	/// Open a signal table from its location within a pod5 file.
+	pub(crate) fn open_at<P: AsRef<Path>>(
+		path: P,
+		offset: u64,
+		length: u64,
+	) -> crate::error::Result<SignalTable>
+	{
+		let file = EmbeddedFile::open(&path, offset, length)?;
+		let reader = FileReader::try_new_buffered(file, None)?;
+
+		// The first row of each batch, followed by the total row count.
+		#[cfg(feature = "native")]
+		let batch_offsets = {
+			let mut file = EmbeddedFile::open(&path, offset, length)?;
+			let mut offsets = vec![0u64];
+			for rows in batch_row_counts(&mut file)?
+			{
+				offsets.push(offsets[offsets.len() - 1] + rows);
+			}
+			offsets
+		};
+
+		Ok(SignalTable {
+			reader,
+			current_batch: None,
+			#[cfg(feature = "native")]
+			batch_offsets,
+		})
+	}
+
+	/// True if the signal column holds VBZ compressed bytes rather than raw samples.
+	pub(crate) fn is_compressed(&self) -> crate::error::Result<bool>
+	{
+		let schema = self.reader.schema();
+		let signal_field = schema.field_with_name("signal").map_err(|_| {
+			crate::error::Error::ArrowCompressionError(
+				"unable to determine signal compression".to_string(),
+			)
+		})?;
+
+		Ok(matches!(
+			signal_field.data_type(),
+			arrow::datatypes::DataType::LargeBinary
+		))
+	}
+
+	pub(crate) fn batch(&mut self, batch_index: usize) -> crate::error::Result<&RecordBatch>
+	{
+		let cached = matches!(&self.current_batch, Some((index, _)) if *index == batch_index);
+		if !cached
+		{
+			self.reader.set_index(batch_index)?;
+			let batch = self.reader.next().ok_or_else(|| {
+				crate::error::Error::IndexError(format!("signal batch {} not found", batch_index))
+			})??;
+			self.current_batch = Some((batch_index, batch));
+		}
+
+		Ok(&self.current_batch.as_ref().unwrap().1)
+	}
+
+	/// The VBZ compressed bytes stored for a single signal row.
+	pub(crate) fn compressed_chunk(
+		&mut self,
+		batch_index: usize,
+		batch_row: usize,
+	) -> crate::error::Result<Vec<u8>>
+	{
+		let batch = self.batch(batch_index)?;
+		let signal = batch
+			.column_by_name("signal")
+			.and_then(|column| column.as_binary_opt::<i64>())
+			.ok_or_else(|| {
+				crate::error::Error::TypeError("signal table is not VBZ compressed".to_string())
+			})?;
+
+		if batch_row >= signal.len()
+		{
+			return Err(crate::error::Error::IndexError(format!(
+				"signal row {} out of range for batch {}",
+				batch_row, batch_index
+			)));
+		}
+
+		Ok(signal.value(batch_row).to_vec())
+	}
+
+	/// Find the batch and batch row of a row index covering the whole signal table.
+	#[cfg(feature = "native")]
+	pub(crate) fn locate(&self, row: u64) -> crate::error::Result<(usize, usize)>
+	{
+		let offsets = &self.batch_offsets;
+		let batch_index = offsets.partition_point(|offset| *offset <= row);
+		if batch_index == 0 || batch_index >= offsets.len()
+		{
+			return Err(crate::error::Error::IndexError(format!(
+				"signal row {} out of range",
+				row
+			)));
+		}
+
+		let batch_index = batch_index - 1;
+		Ok((batch_index, (row - offsets[batch_index]) as usize))
+	}
+
+	/// The number of samples stored in a single signal row.
+	pub(crate) fn sample_count(
+		&mut self,
+		batch_index: usize,
+		batch_row: usize,
+	) -> crate::error::Result<u32>
+	{
+		let batch = self.batch(batch_index)?;
+		let sample_counts = batch
+			.column_by_name("samples")
+			.and_then(|column| column.as_primitive_opt::<UInt32Type>())
+			.ok_or_else(|| {
+				crate::error::Error::TypeError("signal table has no sample counts".to_string())
+			})?;
+
+		if batch_row >= sample_counts.len()
+		{
+			return Err(crate::error::Error::IndexError(format!(
+				"signal row {} out of range for batch {}",
+				batch_row, batch_index
+			)));
+		}
+
+		Ok(sample_counts.value(batch_row))
+	}
+
+	/// The number of bytes used to store a single signal row, after any compression.
+	#[cfg(feature = "native")]
+	pub(crate) fn byte_count(
+		&mut self,
+		batch_index: usize,
+		batch_row: usize,
+	) -> crate::error::Result<usize>
+	{
+		if !self.is_compressed()?
+		{
+			return Ok(self.sample_count(batch_index, batch_row)? as usize * std::mem::size_of::<i16>());
+		}
+
+		let batch = self.batch(batch_index)?;
+		let signal = batch
+			.column_by_name("signal")
+			.and_then(|column| column.as_binary_opt::<i64>())
+			.ok_or_else(|| {
+				crate::error::Error::TypeError("signal table is not VBZ compressed".to_string())
+			})?;
+
+		if batch_row >= signal.len()
+		{
+			return Err(crate::error::Error::IndexError(format!(
+				"signal row {} out of range for batch {}",
+				batch_row, batch_index
+			)));
+		}
+
+		Ok(signal.value_length(batch_row) as usize)
+	}
+
+	/// The uncompressed samples stored for a single signal row.
+	pub(crate) fn samples(
+		&mut self,
+		batch_index: usize,
+		batch_row: usize,
+	) -> crate::error::Result<Vec<i16>>
+	{
+		if self.is_compressed()?
+		{
+			let sample_count = self.sample_count(batch_index, batch_row)?;
+			let data = self.compressed_chunk(batch_index, batch_row)?;
+			return crate::vbz::decompress(&data, sample_count as usize);
+		}
+
+		let batch = self.batch(batch_index)?;
+		let signal = batch
+			.column_by_name("signal")
+			.and_then(|column| column.as_list_opt::<i32>())
+			.ok_or_else(|| {
+				crate::error::Error::TypeError("signal table has no signal column".to_string())
+			})?;
+
+		if batch_row >= signal.len()
+		{
+			return Err(crate::error::Error::IndexError(format!(
+				"signal row {} out of range for batch {}",
+				batch_row, batch_index
+			)));
+		}
+
+		let samples = signal.value(batch_row);
+		let samples = samples.as_primitive_opt::<Int16Type>().ok_or_else(|| {
+			crate::error::Error::TypeError("signal table samples are not 16 bit".to_string())
+		})?;
+
+		Ok(samples.values().to_vec())
+	}
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::read::Read;
+use crate::reader::Reader;
+use crate::writer::{IndexRemap, ReadSource, Writer, WriterOptions};
+
+/// Split reads into multiple pod5 files, routing each read id to its output file.
+///
+/// Reads are visited in file storage order (see [`Reader::reads_iter`]), and signal is
+/// copied without being recompressed. Read ids not found in any open file are skipped.
+///
+/// # Arguments
+///
+/// * `reader` - The reader to take reads from.
+/// * `mapping` - The output file for each read id of interest.
+/// * `options` - the [`WriterOptions`] used for every output file. Pass None to use the defaults (typical).
+///
+/// # Example
+///
+/// ```
+/// let reader = Reader::from_path("sample.pod5", None)?;
+///
+/// let mut mapping = HashMap::new();
+/// mapping.insert(uuid!("002fde30-9e23-4125-9eae-d112c18a81a7"), "barcode01.pod5");
+/// mapping.insert(uuid!("006d1319-2877-4b34-85df-34de7250a47b"), "barcode02.pod5");
+///
+/// let counts = subset(&reader, &mapping, None)?;
+/// ```
+///
+/// # Returns
+///
+/// The number of reads written to each output file.
+pub fn subset<P, W>(
+	reader: &Reader,
+	mapping: &HashMap<uuid::Uuid, P>,
+	options: W,
+) -> crate::error::Result<HashMap<PathBuf, usize>>
+where
+	P: AsRef<Path>,
+	W: Into<Option<WriterOptions>>,
+{
+	let fetch = mapping.keys().copied().collect::<Vec<uuid::Uuid>>();
+
+	subset_reads(reader, Some(fetch), options.into(), |read| {
+		mapping.get(&read.uuid()).map(|path| path.as_ref().to_path_buf())
+	})
+}
+
+/// Split reads into multiple pod5 files, routing each read with a closure.
+///
+/// # Arguments
+///
+/// * `reader` - The reader to take reads from.
+/// * `fetch` - Specify None to consider all reads, or a vector of uuids for specific reads of interest.
+/// * `options` - the [`WriterOptions`] used for every output file. Pass None to use the defaults (typical).
+/// * `route` - Returns the output file for a read, or None to leave the read out.
+///
+/// # Example
+///
+/// ```
+/// let reader = Reader::from_path("sample.pod5", None)?;
+///
+/// let counts = subset_by(&reader, None, None, |read| {
+///     Some(PathBuf::from(format!("channel_{}.pod5", read.channel())))
+/// })?;
+/// ```
+///
+/// # Returns
+///
+/// The number of reads written to each output file.
+pub fn subset_by<U, W, F>(
+	reader: &Reader,
+	fetch: U,
+	options: W,
+	route: F,
+) -> crate::error::Result<HashMap<PathBuf, usize>>
+where
+	U: Into<Option<Vec<uuid::Uuid>>>,
+	W: Into<Option<WriterOptions>>,
+	F: FnMut(&Read) -> Option<PathBuf>,
+{
+	subset_reads(reader, fetch.into(), options.into(), route)
+}
+
+fn subset_reads<F>(
+	reader: &Reader,
+	fetch: Option<Vec<uuid::Uuid>>,
+	options: Option<WriterOptions>,
+	mut route: F,
+) -> crate::error::Result<HashMap<PathBuf, usize>>
+where
+	F: FnMut(&Read) -> Option<PathBuf>,
+{
+	let mut writers: HashMap<PathBuf, Writer> = HashMap::new();
+	let mut counts: HashMap<PathBuf, usize> = HashMap::new();
+
+	for internal_reader in reader.inner.iter()
+	{
+		let mut source = ReadSource::new(internal_reader)?;
+		let mut remaps: HashMap<PathBuf, IndexRemap> = HashMap::new();
+		let reads = crate::reads::Reads::new(std::slice::from_ref(internal_reader), fetch.clone());
+
+		for read in reads
+		{
+			let read = read?;
+			let Some(path) = route(&read)
+			else
+			{
+				continue;
+			};
+
+			if !writers.contains_key(&path)
+			{
+				writers.insert(path.clone(), Writer::from_path(&path, options.clone())?);
+			}
+
+			let writer = writers.get_mut(&path).unwrap();
+			let remap = remaps.entry(path.clone()).or_default();
+			writer.copy_read(&mut source, remap, &read)?;
+
+			*counts.entry(path).or_insert(0) += 1;
+		}
+	}
+
+	for (_, writer) in writers.drain()
+	{
+		writer.close()?;
+	}
+
+	Ok(counts)
+}
@@ -0,0 +1,321 @@
+//! Samples are delta encoded, zig-zag encoded and packed with a 16 bit variant of
+//! streamvbyte, before the packed bytes are compressed with zstd. This matches the
+//! compression applied by the pod5 C++ library, so compressed signal can be decoded
+//! directly from the signal table without going through the C API. The C API's own
+//! implementation is available through `ffi`, unless built with the native backend.
+//!
+//! # Example
+//!
+//! ```
+//! use decapod::vbz;
+//!
+//! let samples = vec![480i16, 482, 479, 1020, 1019];
+//! let compressed = vbz::compress(&samples)?;
+//! let decompressed = vbz::decompress(&compressed, samples.len())?;
+//! assert_eq!(samples, decompressed);
+//! ```
+
+/// zstd level used by the pod5 C++ library.
+const ZSTD_LEVEL: i32 = 1;
+
+fn vbz_error(message: impl std::fmt::Display) -> crate::error::Error
+{
+	crate::error::Error::VbzError(message.to_string())
+}
+
+fn key_length(sample_count: usize) -> usize
+{
+	sample_count.div_ceil(8)
+}
+
+/// The largest size the streamvbyte packed samples can take, before zstd compression.
+fn max_packed_size(sample_count: usize) -> usize
+{
+	key_length(sample_count) + 2 * sample_count
+}
+
+/// The largest size that `sample_count` samples can compress to.
+pub fn max_compressed_size(sample_count: usize) -> usize
+{
+	zstd::zstd_safe::compress_bound(max_packed_size(sample_count))
+}
+
+fn zigzag_encode(value: i16) -> u16
+{
+	((value as u16) << 1) ^ ((value >> 15) as u16)
+}
+
+fn zigzag_decode(value: u16) -> i16
+{
+	((value >> 1) as i16) ^ -((value & 1) as i16)
+}
+
+/// Delta, zig-zag and streamvbyte encode samples.
+///
+/// The output holds one key bit per sample (set when the sample needs two bytes), followed by
+/// the one or two little endian bytes for each sample.
+fn pack(samples: &[i16]) -> Vec<u8>
+{
+	let keys_length = key_length(samples.len());
+	let mut packed = vec![0u8; keys_length];
+	packed.reserve(samples.len() * 2);
+
+	let mut previous = 0i16;
+	for (index, sample) in samples.iter().enumerate()
+	{
+		let value = zigzag_encode(sample.wrapping_sub(previous));
+		previous = *sample;
+
+		if value < 1 << 8
+		{
+			packed.push(value as u8);
+		}
+		else
+		{
+			packed.extend_from_slice(&value.to_le_bytes());
+			packed[index / 8] |= 1 << (index % 8);
+		}
+	}
+
+	packed
+}
+
+/// Reverse [`pack`], writing `samples.len()` samples.
+fn unpack(packed: &[u8], samples: &mut [i16]) -> crate::error::Result<()>
+{
+	let keys_length = key_length(samples.len());
+	if packed.len() < keys_length
+	{
+		return Err(vbz_error("compressed signal is shorter than its keys"));
+	}
+
+	let (keys, mut data) = packed.split_at(keys_length);
+
+	let mut previous = 0i16;
+	for (index, sample) in samples.iter_mut().enumerate()
+	{
+		let value = if keys[index / 8] & (1 << (index % 8)) != 0
+		{
+			let [low, high, rest @ ..] = data
+			else
+			{
+				return Err(vbz_error("compressed signal ended early"));
+			};
+			data = rest;
+			u16::from_le_bytes([*low, *high])
+		}
+		else
+		{
+			let [value, rest @ ..] = data
+			else
+			{
+				return Err(vbz_error("compressed signal ended early"));
+			};
+			data = rest;
+			*value as u16
+		};
+
+		previous = previous.wrapping_add(zigzag_decode(value));
+		*sample = previous;
+	}
+
+	Ok(())
+}
+
+/// Compress samples with VBZ, as stored in a compressed pod5 signal table.
+pub fn compress(samples: &[i16]) -> crate::error::Result<Vec<u8>>
+{
+	zstd::bulk::compress(&pack(samples), ZSTD_LEVEL).map_err(vbz_error)
+}
+
+/// Decompress VBZ compressed signal holding `sample_count` samples.
+pub fn decompress(data: &[u8], sample_count: usize) -> crate::error::Result<Vec<i16>>
+{
+	let mut samples = vec![0i16; sample_count];
+	decompress_into(data, &mut samples)?;
+	Ok(samples)
+}
+
+/// Decompress VBZ compressed signal into an existing slice, which must be exactly the
+/// decompressed sample count long.
+pub fn decompress_into(data: &[u8], samples: &mut [i16]) -> crate::error::Result<()>
+{
+	let packed = zstd::bulk::decompress(data, max_packed_size(samples.len())).map_err(vbz_error)?;
+	unpack(&packed, samples)
+}
+
+/// Safe wrappers around the VBZ functions of the pod5 C API.
+///
+/// These produce the same output as the pure Rust codec, and are mostly useful for
+/// benchmarking or cross checking it.
+#[cfg(feature = "ffi")]
+pub mod ffi
+{
+	/// The largest size that `sample_count` samples can compress to.
+	pub fn max_compressed_size(sample_count: usize) -> usize
+	{
+		let _lock = crate::error::ffi_lock();
+
+		unsafe { crate::pod5_ffi::pod5_vbz_compressed_signal_max_size(sample_count) }
+	}
+
+	/// Compress samples with VBZ using the pod5 C API.
+	pub fn compress(samples: &[i16]) -> crate::error::Result<Vec<u8>>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		let mut compressed_size = max_compressed_size(samples.len());
+		let mut compressed = vec![0u8; compressed_size];
+
+		unsafe {
+			crate::pod5_ffi::pod5_vbz_compress_signal(
+				samples.as_ptr(),
+				samples.len(),
+				compressed.as_mut_ptr() as *mut std::os::raw::c_char,
+				&mut compressed_size,
+			);
+		}
+
+		crate::pod5_check_result!();
+
+		compressed.truncate(compressed_size);
+		Ok(compressed)
+	}
+
+	/// Decompress VBZ compressed signal holding `sample_count` samples using the pod5 C API.
+	pub fn decompress(data: &[u8], sample_count: usize) -> crate::error::Result<Vec<i16>>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		let mut samples = vec![0i16; sample_count];
+
+		unsafe {
+			crate::pod5_ffi::pod5_vbz_decompress_signal(
+				data.as_ptr() as *const std::os::raw::c_char,
+				data.len(),
+				sample_count,
+				samples.as_mut_ptr(),
+			);
+		}
+
+		crate::pod5_ok!(samples)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	/// Deterministic signal generator, so failures can be reproduced.
+	fn random_signal(seed: u64, length: usize, spread: i32) -> Vec<i16>
+	{
+		let mut state = seed;
+		let mut sample = 500i32;
+		(0..length)
+			.map(|_| {
+				state ^= state << 13;
+				state ^= state >> 7;
+				state ^= state << 17;
+				sample += (state % (2 * spread as u64 + 1)) as i32 - spread;
+				sample = sample.clamp(i16::MIN as i32, i16::MAX as i32);
+				sample as i16
+			})
+			.collect()
+	}
+
+	fn edge_case_signals() -> Vec<Vec<i16>>
+	{
+		vec![
+			vec![],
+			vec![0],
+			vec![i16::MIN],
+			vec![i16::MAX],
+			vec![i16::MIN, i16::MAX, i16::MIN, i16::MAX],
+			vec![i16::MAX, i16::MIN, 0, -1, 1, i16::MAX],
+			vec![-1; 9],
+			(i16::MIN..=i16::MAX).step_by(257).collect(),
+		]
+	}
+
+	fn test_signals() -> Vec<Vec<i16>>
+	{
+		let mut signals = edge_case_signals();
+		for (seed, length, spread) in [(1, 7, 5), (2, 8, 100), (3, 4000, 20), (4, 10001, 30000)]
+		{
+			signals.push(random_signal(seed, length, spread));
+		}
+		signals
+	}
+
+	#[test]
+	fn zigzag()
+	{
+		for (value, encoded) in [(0, 0), (-1, 1), (1, 2), (-2, 3), (i16::MAX, 0xfffe), (i16::MIN, 0xffff)]
+		{
+			assert_eq!(zigzag_encode(value), encoded);
+			assert_eq!(zigzag_decode(encoded), value);
+		}
+	}
+
+	#[test]
+	fn pack_known_bytes()
+	{
+		// Deltas 1, -2, 301, 0 zig-zag to 2, 3, 602, 0, and only 602 needs two bytes.
+		assert_eq!(pack(&[1, -1, 300, 300]), vec![0b0000_0100, 2, 3, 0x5a, 0x02, 0]);
+
+		// A ninth sample starts a second key byte.
+		assert_eq!(
+			pack(&[0, 0, 0, 0, 0, 0, 0, 0, i16::MIN]),
+			vec![0, 0b0000_0001, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff]
+		);
+
+		// The delta from i16::MAX to i16::MIN wraps around to 1.
+		assert_eq!(pack(&[i16::MAX, i16::MIN]), vec![0b0000_0001, 0xfe, 0xff, 2]);
+		assert_eq!(pack(&[]), Vec::<u8>::new());
+	}
+
+	#[test]
+	fn round_trip()
+	{
+		for samples in test_signals()
+		{
+			let compressed = compress(&samples).unwrap();
+			assert!(compressed.len() <= max_compressed_size(samples.len()));
+			assert_eq!(decompress(&compressed, samples.len()).unwrap(), samples);
+
+			let mut packed_samples = vec![0i16; samples.len()];
+			unpack(&pack(&samples), &mut packed_samples).unwrap();
+			assert_eq!(packed_samples, samples);
+		}
+	}
+
+	#[test]
+	fn truncated_input()
+	{
+		let packed = pack(&[1, -1, 300, 300]);
+		let mut samples = [0i16; 4];
+		assert!(unpack(&packed[..packed.len() - 1], &mut samples).is_err());
+		assert!(unpack(&[], &mut samples).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "ffi")]
+	fn matches_ffi()
+	{
+		for samples in test_signals()
+		{
+			let ffi_compressed = ffi::compress(&samples).unwrap();
+			let compressed = compress(&samples).unwrap();
+
+			// zstd output can vary between versions, so compare the packed bytes under it.
+			let ffi_packed =
+				zstd::bulk::decompress(&ffi_compressed, max_packed_size(samples.len())).unwrap();
+			assert_eq!(ffi_packed, pack(&samples));
+
+			assert_eq!(decompress(&ffi_compressed, samples.len()).unwrap(), samples);
+			assert_eq!(ffi::decompress(&compressed, samples.len()).unwrap(), samples);
+			assert_eq!(ffi::max_compressed_size(samples.len()), max_compressed_size(samples.len()));
+		}
+	}
+}
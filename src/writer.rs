@@ -0,0 +1,762 @@
+use std::ffi::{c_char, c_void, CString};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::read::{Read, ReadData, SignalChunk};
+use crate::runinfo::RunInfoData;
+
+const WRITER_NAME: &str = "decapod";
+
+fn c_string(value: &str) -> crate::error::Result<CString>
+{
+	CString::new(value).map_err(|_| {
+		crate::error::Error::InvalidError(format!("string contains a nul byte: {}", value))
+	})
+}
+
+fn c_key_values(
+	map: &HashMap<String, String>,
+) -> crate::error::Result<(Vec<CString>, Vec<CString>)>
+{
+	let mut keys = Vec::with_capacity(map.len());
+	let mut values = Vec::with_capacity(map.len());
+	for (key, value) in map
+	{
+		keys.push(c_string(key)?);
+		values.push(c_string(value)?);
+	}
+
+	Ok((keys, values))
+}
+
+/// Signal compression to use for the signal table of a written pod5 file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompressionOption
+{
+	/// Let the pod5 library decide, currently VBZ.
+	#[default]
+	Default,
+	/// VBZ compressed signal.
+	Vbz,
+	/// Uncompressed signal, useful for debugging.
+	Uncompressed,
+}
+
+impl CompressionOption
+{
+	pub(crate) fn to_ffi(self) -> i8
+	{
+		match self
+		{
+			CompressionOption::Default =>
+			{
+				crate::pod5_ffi::CompressionOption_DEFAULT_SIGNAL_COMPRESSION as i8
+			}
+			CompressionOption::Vbz => crate::pod5_ffi::CompressionOption_VBZ_SIGNAL_COMPRESSION as i8,
+			CompressionOption::Uncompressed =>
+			{
+				crate::pod5_ffi::CompressionOption_UNCOMPRESSED_SIGNAL as i8
+			}
+		}
+	}
+}
+
+/// Contains writer options, passed to Writer.
+///
+/// Any option left unset (or set to zero) uses the pod5 library default.
+///
+/// # Example
+///
+/// ```
+/// use decapod::writer::{CompressionOption, Writer, WriterOptions};
+///
+/// let options = WriterOptions::new()
+///     .signal_compression(CompressionOption::Uncompressed)
+///     .read_table_batch_size(1000);
+/// let writer = Writer::from_path("output.pod5", options)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WriterOptions
+{
+	max_signal_chunk_size: u32,
+	signal_compression: CompressionOption,
+	signal_table_batch_size: usize,
+	read_table_batch_size: usize,
+}
+
+impl WriterOptions
+{
+	/// Create a writer options struct with every option set to the pod5 library default.
+	pub fn new() -> WriterOptions
+	{
+		WriterOptions::default()
+	}
+
+	/// Maximum number of samples stored in a single signal table row.
+	pub fn max_signal_chunk_size(mut self, max_signal_chunk_size: u32) -> WriterOptions
+	{
+		self.max_signal_chunk_size = max_signal_chunk_size;
+		self
+	}
+
+	/// Compression used for the signal table, see [`CompressionOption`].
+	pub fn signal_compression(mut self, signal_compression: CompressionOption) -> WriterOptions
+	{
+		self.signal_compression = signal_compression;
+		self
+	}
+
+	/// Number of signal rows written per signal table batch.
+	pub fn signal_table_batch_size(mut self, signal_table_batch_size: usize) -> WriterOptions
+	{
+		self.signal_table_batch_size = signal_table_batch_size;
+		self
+	}
+
+	/// Number of reads written per read table batch.
+	pub fn read_table_batch_size(mut self, read_table_batch_size: usize) -> WriterOptions
+	{
+		self.read_table_batch_size = read_table_batch_size;
+		self
+	}
+
+	pub(crate) fn to_ffi(&self) -> crate::pod5_ffi::Pod5WriterOptions_t
+	{
+		crate::pod5_ffi::Pod5WriterOptions_t {
+			max_signal_chunk_size: self.max_signal_chunk_size,
+			signal_compression_type: self.signal_compression.to_ffi(),
+			signal_table_batch_size: self.signal_table_batch_size,
+			read_table_batch_size: self.read_table_batch_size,
+		}
+	}
+}
+
+/// Per source file state used when copying reads into one or more [`Writer`]s.
+pub(crate) struct ReadSource
+{
+	signal_table: Option<crate::signaltable::SignalTable>,
+}
+
+impl ReadSource
+{
+	pub(crate) fn new(reader: &crate::reader::InternalReader) -> crate::error::Result<ReadSource>
+	{
+		let signal_table = if reader.has_compression
+		{
+			Some(reader.signal_table()?)
+		}
+		else
+		{
+			None
+		};
+
+		Ok(ReadSource { signal_table })
+	}
+}
+
+/// Maps the pore and run info indices of a source file to those of the file being written.
+#[derive(Default)]
+pub(crate) struct IndexRemap
+{
+	pores: HashMap<i16, i16>,
+	run_infos: HashMap<i16, i16>,
+}
+
+/// Column storage for a group of reads, laid out as expected by `ReadBatchRowInfoArray_t`.
+pub(crate) struct ReadDataArray
+{
+	read_id: Vec<[u8; 16]>,
+	read_number: Vec<u32>,
+	start_sample: Vec<u64>,
+	median_before: Vec<f32>,
+	channel: Vec<u16>,
+	well: Vec<u8>,
+	pore_type: Vec<i16>,
+	calibration_offset: Vec<f32>,
+	calibration_scale: Vec<f32>,
+	end_reason: Vec<crate::pod5_ffi::pod5_end_reason_t>,
+	end_reason_forced: Vec<u8>,
+	run_info_id: Vec<i16>,
+	num_minknow_events: Vec<u64>,
+	tracked_scaling_scale: Vec<f32>,
+	tracked_scaling_shift: Vec<f32>,
+	predicted_scaling_scale: Vec<f32>,
+	predicted_scaling_shift: Vec<f32>,
+	num_reads_since_mux_change: Vec<u32>,
+	time_since_mux_change: Vec<f32>,
+}
+
+impl ReadDataArray
+{
+	pub(crate) fn from_reads<'a, I>(reads: I) -> ReadDataArray
+	where
+		I: IntoIterator<Item = &'a ReadData>,
+	{
+		let mut array = ReadDataArray {
+			read_id: Vec::new(),
+			read_number: Vec::new(),
+			start_sample: Vec::new(),
+			median_before: Vec::new(),
+			channel: Vec::new(),
+			well: Vec::new(),
+			pore_type: Vec::new(),
+			calibration_offset: Vec::new(),
+			calibration_scale: Vec::new(),
+			end_reason: Vec::new(),
+			end_reason_forced: Vec::new(),
+			run_info_id: Vec::new(),
+			num_minknow_events: Vec::new(),
+			tracked_scaling_scale: Vec::new(),
+			tracked_scaling_shift: Vec::new(),
+			predicted_scaling_scale: Vec::new(),
+			predicted_scaling_shift: Vec::new(),
+			num_reads_since_mux_change: Vec::new(),
+			time_since_mux_change: Vec::new(),
+		};
+
+		for read in reads
+		{
+			array.read_id.push(*read.read_id.as_bytes());
+			array.read_number.push(read.read_number);
+			array.start_sample.push(read.start_sample);
+			array.median_before.push(read.median_before);
+			array.channel.push(read.channel);
+			array.well.push(read.well);
+			array.pore_type.push(read.pore_type);
+			array.calibration_offset.push(read.calibration_offset);
+			array.calibration_scale.push(read.calibration_scale);
+			array.end_reason.push(read.end_reason as crate::pod5_ffi::pod5_end_reason_t);
+			array.end_reason_forced.push(read.end_reason_forced as u8);
+			array.run_info_id.push(read.run_info);
+			array.num_minknow_events.push(read.num_minknow_events);
+			array.tracked_scaling_scale.push(read.tracked_scaling_scale);
+			array.tracked_scaling_shift.push(read.tracked_scaling_shift);
+			array.predicted_scaling_scale.push(read.predicted_scaling_scale);
+			array.predicted_scaling_shift.push(read.predicted_scaling_shift);
+			array.num_reads_since_mux_change.push(read.num_reads_since_mux_change);
+			array.time_since_mux_change.push(read.time_since_mux_change);
+		}
+
+		array
+	}
+
+	pub(crate) fn len(&self) -> usize
+	{
+		self.read_id.len()
+	}
+
+	/// The returned struct borrows the column storage, so must not outlive `self`.
+	pub(crate) fn to_ffi(&self) -> crate::pod5_ffi::ReadBatchRowInfoArray_t
+	{
+		crate::pod5_ffi::ReadBatchRowInfoArray_t {
+			read_id: self.read_id.as_ptr(),
+			read_number: self.read_number.as_ptr(),
+			start_sample: self.start_sample.as_ptr(),
+			median_before: self.median_before.as_ptr(),
+			channel: self.channel.as_ptr(),
+			well: self.well.as_ptr(),
+			pore_type: self.pore_type.as_ptr(),
+			calibration_offset: self.calibration_offset.as_ptr(),
+			calibration_scale: self.calibration_scale.as_ptr(),
+			end_reason: self.end_reason.as_ptr(),
+			end_reason_forced: self.end_reason_forced.as_ptr(),
+			run_info_id: self.run_info_id.as_ptr(),
+			num_minknow_events: self.num_minknow_events.as_ptr(),
+			tracked_scaling_scale: self.tracked_scaling_scale.as_ptr(),
+			tracked_scaling_shift: self.tracked_scaling_shift.as_ptr(),
+			predicted_scaling_scale: self.predicted_scaling_scale.as_ptr(),
+			predicted_scaling_shift: self.predicted_scaling_shift.as_ptr(),
+			num_reads_since_mux_change: self.num_reads_since_mux_change.as_ptr(),
+			time_since_mux_change: self.time_since_mux_change.as_ptr(),
+		}
+	}
+}
+
+/// Create a new pod5 file and write pores, run info and reads to it.
+///
+/// The file is finalised when the writer is dropped, or explicitly with [`Writer::close`]
+/// to observe any error raised while flushing the file.
+///
+/// End reasons do not need registering, they are written directly from
+/// [`crate::endreason::EndReason`].
+///
+/// # Example
+///
+/// ```
+/// use decapod::writer::Writer;
+/// use decapod::reader::ReadData;
+/// use decapod::runinfo::RunInfoData;
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>>
+/// {
+///     let mut writer = Writer::from_path("output.pod5", None)?;
+///
+///     let pore_type = writer.add_pore("not_set")?;
+///     let run_info = writer.add_run_info(&RunInfoData {
+///         acquisition_id: "acquisition".to_string(),
+///         sample_rate: 5000,
+///         ..Default::default()
+///     })?;
+///
+///     writer.add_read(&ReadData {
+///         read_id: uuid::Uuid::new_v4(),
+///         channel: 1,
+///         well: 1,
+///         pore_type,
+///         run_info,
+///         signal: vec![0i16; 100],
+///         ..Default::default()
+///     })?;
+///
+///     writer.close()?;
+///     Ok(())
+/// }
+/// ```
+pub struct Writer
+{
+	pub(crate) inner: *mut crate::pod5_ffi::Pod5FileWriter_t,
+
+	// Pores and run info added while copying reads from other files, so each is only written
+	// once however many source files share it.
+	copied_pores: HashMap<String, i16>,
+	copied_run_infos: HashMap<String, i16>,
+}
+
+impl Writer
+{
+	/// Creates a new pod5 file for writing.
+	/// # Arguments
+	///
+	/// * `path` - The path of the pod5 file to create.
+	/// * `options` - the [`WriterOptions`] object. Pass None to use the pod5 library defaults (typical).
+	pub fn from_path<P: AsRef<Path>, W: Into<Option<WriterOptions>>>(
+		path: P,
+		options: W,
+	) -> crate::error::Result<Writer>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		let options = options.into().map(|options| options.to_ffi());
+
+		let c_path = path
+			.as_ref()
+			.to_str()
+			.ok_or_else(|| crate::error::Error::MemoryError("memory error".to_string()))
+			.and_then(|s| {
+				CString::new(s)
+					.map_err(|_| crate::error::Error::MemoryError("memory error".to_string()))
+			})?;
+		let c_writer_name = c_string(WRITER_NAME)?;
+
+		unsafe {
+			crate::pod5_ffi::pod5_init();
+		}
+
+		let ptr = unsafe {
+			crate::pod5_ffi::pod5_create_file(
+				c_path.as_ptr(),
+				c_writer_name.as_ptr(),
+				options
+					.as_ref()
+					.map_or(std::ptr::null(), |options| options as *const _),
+			)
+		};
+
+		let writer = Writer {
+			inner: ptr,
+			copied_pores: HashMap::new(),
+			copied_run_infos: HashMap::new(),
+		};
+
+		crate::pod5_ok!(writer)
+	}
+
+	/// Adds a pore type to the file, returning the index to use as [`ReadData::pore_type`].
+	pub fn add_pore(&mut self, pore_type: &str) -> crate::error::Result<i16>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		let c_pore_type = c_string(pore_type)?;
+		let mut pore_index: i16 = 0;
+
+		unsafe {
+			crate::pod5_ffi::pod5_add_pore(&mut pore_index, self.inner, c_pore_type.as_ptr());
+		}
+
+		crate::pod5_ok!(pore_index)
+	}
+
+	/// Adds run info to the file, returning the index to use as [`ReadData::run_info`].
+	pub fn add_run_info(&mut self, run_info: &RunInfoData) -> crate::error::Result<i16>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		let acquisition_id = c_string(&run_info.acquisition_id)?;
+		let experiment_name = c_string(&run_info.experiment_name)?;
+		let flow_cell_id = c_string(&run_info.flow_cell_id)?;
+		let flow_cell_product_code = c_string(&run_info.flow_cell_product_code)?;
+		let protocol_name = c_string(&run_info.protocol_name)?;
+		let protocol_run_id = c_string(&run_info.protocol_run_id)?;
+		let sample_id = c_string(&run_info.sample_id)?;
+		let sequencing_kit = c_string(&run_info.sequencing_kit)?;
+		let sequencer_position = c_string(&run_info.sequencer_position)?;
+		let sequencer_position_type = c_string(&run_info.sequencer_position_type)?;
+		let software = c_string(&run_info.software)?;
+		let system_name = c_string(&run_info.system_name)?;
+		let system_type = c_string(&run_info.system_type)?;
+
+		let (context_keys, context_values) = c_key_values(&run_info.context_tags)?;
+		let mut context_keys: Vec<*const c_char> = context_keys.iter().map(|s| s.as_ptr()).collect();
+		let mut context_values: Vec<*const c_char> =
+			context_values.iter().map(|s| s.as_ptr()).collect();
+
+		let (tracking_keys, tracking_values) = c_key_values(&run_info.tracking_id)?;
+		let mut tracking_keys: Vec<*const c_char> =
+			tracking_keys.iter().map(|s| s.as_ptr()).collect();
+		let mut tracking_values: Vec<*const c_char> =
+			tracking_values.iter().map(|s| s.as_ptr()).collect();
+
+		let mut run_info_index: i16 = 0;
+
+		unsafe {
+			crate::pod5_ffi::pod5_add_run_info(
+				&mut run_info_index,
+				self.inner,
+				acquisition_id.as_ptr(),
+				run_info.acquisition_start_time_ms,
+				run_info.adc_max,
+				run_info.adc_min,
+				context_keys.len(),
+				context_keys.as_mut_ptr(),
+				context_values.as_mut_ptr(),
+				experiment_name.as_ptr(),
+				flow_cell_id.as_ptr(),
+				flow_cell_product_code.as_ptr(),
+				protocol_name.as_ptr(),
+				protocol_run_id.as_ptr(),
+				run_info.protocol_start_time_ms,
+				sample_id.as_ptr(),
+				run_info.sample_rate,
+				sequencing_kit.as_ptr(),
+				sequencer_position.as_ptr(),
+				sequencer_position_type.as_ptr(),
+				software.as_ptr(),
+				system_name.as_ptr(),
+				system_type.as_ptr(),
+				tracking_keys.len(),
+				tracking_keys.as_mut_ptr(),
+				tracking_values.as_mut_ptr(),
+			);
+		}
+
+		crate::pod5_ok!(run_info_index)
+	}
+
+	/// Adds a single read, including its signal, to the file.
+	pub fn add_read(&mut self, read: &ReadData) -> crate::error::Result<()>
+	{
+		self.add_reads(std::slice::from_ref(read))
+	}
+
+	/// Adds a group of reads, including their signal, to the file.
+	pub fn add_reads(&mut self, reads: &[ReadData]) -> crate::error::Result<()>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		if reads.is_empty()
+		{
+			return Ok(());
+		}
+
+		let array = ReadDataArray::from_reads(reads);
+		let row_data = array.to_ffi();
+
+		let mut signal: Vec<*const i16> = reads.iter().map(|read| read.signal.as_ptr()).collect();
+		let signal_size: Vec<u32> = reads.iter().map(|read| read.signal.len() as u32).collect();
+
+		unsafe {
+			crate::pod5_ffi::pod5_add_reads_data(
+				self.inner,
+				array.len() as u32,
+				crate::pod5_ffi::READ_BATCH_ROW_INFO_VERSION as u16,
+				&row_data as *const crate::pod5_ffi::ReadBatchRowInfoArray_t as *const c_void,
+				signal.as_mut_ptr(),
+				signal_size.as_ptr(),
+			);
+		}
+
+		crate::pod5_ok!(())
+	}
+
+	/// Adds a group of reads whose signal is already VBZ compressed, avoiding the cost of
+	/// compressing the signal again.
+	///
+	/// `signal` holds the compressed chunks for each read in `reads`, the `signal` field of
+	/// each [`ReadData`] is ignored. Chunks can be compressed with the pod5 VBZ codec, or
+	/// copied from an existing file with [`Writer::add_reads_from`].
+	pub fn add_reads_pre_compressed(
+		&mut self,
+		reads: &[ReadData],
+		signal: &[Vec<SignalChunk>],
+	) -> crate::error::Result<()>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		if reads.len() != signal.len()
+		{
+			return Err(crate::error::Error::InvalidError(format!(
+				"{} reads provided with signal for {} reads",
+				reads.len(),
+				signal.len()
+			)));
+		}
+
+		if reads.is_empty()
+		{
+			return Ok(());
+		}
+
+		let array = ReadDataArray::from_reads(reads);
+		let row_data = array.to_ffi();
+
+		let mut chunk_data: Vec<Vec<*const c_char>> = signal
+			.iter()
+			.map(|chunks| {
+				chunks
+					.iter()
+					.map(|chunk| chunk.data.as_ptr() as *const c_char)
+					.collect()
+			})
+			.collect();
+		let chunk_sizes: Vec<Vec<usize>> = signal
+			.iter()
+			.map(|chunks| chunks.iter().map(|chunk| chunk.data.len()).collect())
+			.collect();
+		let sample_counts: Vec<Vec<u32>> = signal
+			.iter()
+			.map(|chunks| chunks.iter().map(|chunk| chunk.sample_count).collect())
+			.collect();
+
+		let mut compressed_signal: Vec<*mut *const c_char> =
+			chunk_data.iter_mut().map(|chunks| chunks.as_mut_ptr()).collect();
+		let mut compressed_signal_size: Vec<*const usize> =
+			chunk_sizes.iter().map(|sizes| sizes.as_ptr()).collect();
+		let mut sample_counts: Vec<*const u32> =
+			sample_counts.iter().map(|counts| counts.as_ptr()).collect();
+		let signal_chunk_count: Vec<usize> = signal.iter().map(|chunks| chunks.len()).collect();
+
+		unsafe {
+			crate::pod5_ffi::pod5_add_reads_data_pre_compressed(
+				self.inner,
+				array.len() as u32,
+				crate::pod5_ffi::READ_BATCH_ROW_INFO_VERSION as u16,
+				&row_data as *const crate::pod5_ffi::ReadBatchRowInfoArray_t as *const c_void,
+				compressed_signal.as_mut_ptr(),
+				compressed_signal_size.as_mut_ptr(),
+				sample_counts.as_mut_ptr(),
+				signal_chunk_count.as_ptr(),
+			);
+		}
+
+		crate::pod5_ok!(())
+	}
+
+	/// Copies reads from all files open in `reader` into this file.
+	///
+	/// Pore types and run info used by the copied reads are added to this file as required.
+	/// VBZ compressed signal is moved across as-is without being decompressed, uncompressed
+	/// signal is written as if added with [`Writer::add_reads`].
+	///
+	/// # Arguments
+	///
+	/// * `reader` - The reader to copy reads from.
+	/// * `fetch` - Specify None to copy all reads, or a vector of uuids for specific reads of interest.
+	///
+	/// # Example
+	///
+	/// ```
+	/// let reader = Reader::from_path("sample.pod5", None)?;
+	/// let mut writer = Writer::from_path("subset.pod5", None)?;
+	/// let read_ids = vec![uuid!("002fde30-9e23-4125-9eae-d112c18a81a7")];
+	/// writer.add_reads_from(&reader, read_ids)?;
+	/// ```
+	///
+	/// # Returns
+	///
+	/// The number of reads copied.
+	pub fn add_reads_from<U: Into<Option<Vec<uuid::Uuid>>>>(
+		&mut self,
+		reader: &crate::reader::Reader,
+		fetch: U,
+	) -> crate::error::Result<usize>
+	{
+		let fetch = fetch.into();
+		let mut count = 0;
+
+		for internal_reader in reader.inner.iter()
+		{
+			let mut source = ReadSource::new(internal_reader)?;
+			let mut remap = IndexRemap::default();
+			let reads = crate::reads::Reads::new(std::slice::from_ref(internal_reader), fetch.clone());
+
+			for read in reads
+			{
+				self.copy_read(&mut source, &mut remap, &read?)?;
+				count += 1;
+			}
+		}
+
+		Ok(count)
+	}
+
+	/// Adds every run info from `reader` to this file, whether or not any reads using it are
+	/// copied.
+	pub(crate) fn copy_run_infos(
+		&mut self,
+		reader: &crate::reader::InternalReader,
+		remap: &mut IndexRemap,
+	) -> crate::error::Result<()>
+	{
+		let run_infos = crate::runinfo::RunInfoIter {
+			rows: 0,
+			reader: std::slice::from_ref(reader).iter(),
+			current_row: 0,
+			current_reader: None,
+		};
+
+		for (index, run_info) in run_infos.enumerate()
+		{
+			let run_info_index = self.copy_run_info(&run_info?.to_owned()?)?;
+			remap.run_infos.insert(index as i16, run_info_index);
+		}
+
+		Ok(())
+	}
+
+	/// Adds every pore type from `reader` to this file, whether or not any reads using it are
+	/// copied.
+	pub(crate) fn copy_pores(
+		&mut self,
+		reader: &crate::reader::InternalReader,
+		remap: &mut IndexRemap,
+	) -> crate::error::Result<()>
+	{
+		for (index, pore_type) in reader.pore_types()?.iter().enumerate()
+		{
+			let pore_index = self.copy_pore(pore_type)?;
+			remap.pores.insert(index as i16, pore_index);
+		}
+
+		Ok(())
+	}
+
+	/// Adds a pore type copied from another file, reusing the index of a pore type that has
+	/// already been copied.
+	fn copy_pore(&mut self, pore_type: &str) -> crate::error::Result<i16>
+	{
+		if let Some(pore_index) = self.copied_pores.get(pore_type)
+		{
+			return Ok(*pore_index);
+		}
+
+		let pore_index = self.add_pore(pore_type)?;
+		self.copied_pores.insert(pore_type.to_string(), pore_index);
+		Ok(pore_index)
+	}
+
+	/// Adds run info copied from another file, reusing the index of run info with the same
+	/// acquisition id that has already been copied.
+	fn copy_run_info(&mut self, run_info: &RunInfoData) -> crate::error::Result<i16>
+	{
+		if let Some(run_info_index) = self.copied_run_infos.get(&run_info.acquisition_id)
+		{
+			return Ok(*run_info_index);
+		}
+
+		let run_info_index = self.add_run_info(run_info)?;
+		self.copied_run_infos.insert(run_info.acquisition_id.clone(), run_info_index);
+		Ok(run_info_index)
+	}
+
+	pub(crate) fn copy_read(
+		&mut self,
+		source: &mut ReadSource,
+		remap: &mut IndexRemap,
+		read: &Read,
+	) -> crate::error::Result<()>
+	{
+		let pore_type = match remap.pores.get(&read.pore_type())
+		{
+			Some(pore_type) => *pore_type,
+			None =>
+			{
+				let pore_type = self.copy_pore(&read.pore_type_string()?)?;
+				remap.pores.insert(read.pore_type(), pore_type);
+				pore_type
+			}
+		};
+
+		let run_info = match remap.run_infos.get(&read.run_info_num())
+		{
+			Some(run_info) => *run_info,
+			None =>
+			{
+				let run_info = self.copy_run_info(&read.run_info()?.to_owned()?)?;
+				remap.run_infos.insert(read.run_info_num(), run_info);
+				run_info
+			}
+		};
+
+		match source.signal_table.as_mut()
+		{
+			Some(signal_table) =>
+			{
+				let mut read_data = read.read_data(Vec::new());
+				read_data.pore_type = pore_type;
+				read_data.run_info = run_info;
+
+				let signal = read.compressed_signal(signal_table)?;
+				self.add_reads_pre_compressed(std::slice::from_ref(&read_data), &[signal])
+			}
+			None =>
+			{
+				let mut read_data = read.to_owned()?;
+				read_data.pore_type = pore_type;
+				read_data.run_info = run_info;
+
+				self.add_read(&read_data)
+			}
+		}
+	}
+
+	/// Finalises the pod5 file, returning any error raised while doing so.
+	pub fn close(mut self) -> crate::error::Result<()>
+	{
+		let _lock = crate::error::ffi_lock();
+
+		unsafe {
+			crate::pod5_ffi::pod5_close_and_free_writer(self.inner);
+		}
+
+		self.inner = std::ptr::null_mut();
+
+		crate::pod5_ok!(())
+	}
+}
+
+impl Drop for Writer
+{
+	fn drop(&mut self)
+	{
+		let _lock = crate::error::ffi_lock();
+
+		unsafe {
+			if !self.inner.is_null()
+			{
+				crate::pod5_ffi::pod5_close_and_free_writer(self.inner);
+			}
+			crate::pod5_ffi::pod5_terminate();
+		}
+	}
+}